@@ -198,6 +198,10 @@ impl<'ast> Visitor<'ast> for Scanner<'_> {
                 nc.in_constant_id = true;
                 e.recurse(nc, self)
             }
+            aast::Expr_::MethodCaller(_) => {
+                nc.in_class_id = true;
+                e.recurse(nc, self)
+            }
             aast::Expr_::EnumClassLabel(boxed) => {
                 if boxed.0.is_some() {
                     nc.in_class_id = true;
@@ -86,13 +86,16 @@ impl<'a> ClassLikeAnalyzer<'a> {
             DataFlowGraph::new(statements_analyzer.get_config().graph_kind),
             statements_analyzer.get_file_analyzer().get_file_source(),
             &statements_analyzer.comments,
-            &statements_analyzer.get_config().all_custom_issues,
+            statements_analyzer.get_config(),
             None,
             classlike_storage.meta_start.start_offset,
             None,
         );
 
-        if let Some(issue_filter) = &statements_analyzer.get_config().allowed_issues {
+        if let Some(issue_filter) = statements_analyzer
+            .get_config()
+            .get_allowed_issues(statements_analyzer.get_file_path_actual())
+        {
             analysis_data.issue_filter = Some(issue_filter.clone());
         }
 
@@ -218,7 +221,9 @@ impl<'a> ClassLikeAnalyzer<'a> {
             analysis_data,
             analysis_result,
             statements_analyzer.get_file_path(),
+            (name, StrId::EMPTY),
             false,
+            statements_analyzer.get_config().in_language_server,
         );
 
         for method in &stmt.methods {
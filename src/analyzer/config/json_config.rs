@@ -1,11 +1,18 @@
 use std::{error::Error, fs::File, io::BufReader, path::Path};
 
+use hakana_reflection_info::issue::IssueSeverity;
 use rustc_hash::FxHashMap;
 use serde::Deserialize;
 
 #[derive(Deserialize, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct JsonConfig {
+    // another config file, relative to this one, whose settings this one builds on
+    #[serde(default)]
+    pub extends: Option<String>,
+    // directories with their own hakana.json that overrides this one for files inside them
+    #[serde(default)]
+    pub directory_configs: Vec<String>,
     #[serde(default)]
     pub ignore_files: Vec<String>,
     #[serde(default)]
@@ -16,12 +23,39 @@ pub struct JsonConfig {
     pub allowed_issues: Vec<String>,
     #[serde(default)]
     pub test_files: Vec<String>,
+    #[serde(default)]
+    pub issue_severity: FxHashMap<String, IssueSeverity>,
+    #[serde(default)]
+    pub issue_severity_files: FxHashMap<String, FxHashMap<String, IssueSeverity>>,
 }
 
 #[derive(Deserialize, Debug, Default)]
 pub struct JsonSecurityConfig {
+    #[serde(default)]
     pub ignore_files: Vec<String>,
+    #[serde(default)]
     pub ignore_sink_files: FxHashMap<String, Vec<String>>,
+    // taint spec files, relative to this config file
+    #[serde(default)]
+    pub taint_specs: Vec<String>,
+    #[serde(default)]
+    pub taint_policy: Option<JsonTaintPolicy>,
+    // org-specific sources, keyed by name, with a description used in issue messages
+    #[serde(default)]
+    pub custom_sources: FxHashMap<String, String>,
+}
+
+// flows are written as "Source -> Sink", with custom sinks written as "Custom:Name"
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct JsonTaintPolicy {
+    // start from an empty set of forbidden flows, rather than Hakana's defaults
+    #[serde(default)]
+    pub ignore_default_flows: bool,
+    #[serde(default)]
+    pub forbidden_flows: Vec<String>,
+    #[serde(default)]
+    pub allowed_flows: Vec<String>,
 }
 
 pub(crate) fn read_from_file(path: &Path) -> Result<JsonConfig, Box<dyn Error>> {
@@ -1,34 +1,55 @@
-use std::{error::Error, path::Path};
+use std::{
+    error::Error,
+    fmt,
+    hash::{Hash, Hasher},
+    path::{Component, Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+    time::Duration,
+};
 
 use hakana_reflection_info::{
     data_flow::{graph::GraphKind, tainted_node::TaintedNode},
-    issue::{Issue, IssueKind},
-    taint::{SinkType, SourceType},
+    issue::{Issue, IssueKind, IssueSeverity},
+    taint::{SinkType, SourceType, TaintPolicy},
 };
 use hakana_str::Interner;
-use rustc_hash::{FxHashMap, FxHashSet};
+use rustc_hash::{FxHashMap, FxHashSet, FxHasher};
+use strum::VariantNames;
 
 use crate::custom_hook::CustomHook;
 
+use self::query::QueryNode;
+use self::taint_spec::TaintSpec;
+
 pub mod json_config;
+pub mod query;
+pub mod taint_spec;
 
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Config {
     pub migration_symbols: FxHashMap<String, String>,
     pub in_migration: bool,
     pub in_codegen: bool,
+    pub in_language_server: bool,
     pub find_unused_expressions: bool,
     pub find_unused_definitions: bool,
     pub allowed_issues: Option<FxHashSet<IssueKind>>,
+    // per-directory overrides of allowed_issues, most specific directory first
+    pub directory_allowed_issues: Vec<(String, FxHashSet<IssueKind>)>,
     pub issues_to_fix: FxHashSet<IssueKind>,
     pub graph_kind: GraphKind,
     pub ignore_files: Vec<String>,
     pub test_files: Vec<String>,
     pub ignore_issue_files: FxHashMap<IssueKind, Vec<String>>,
     pub ignore_all_issues_in_files: Vec<String>,
+    pub issue_severities: FxHashMap<IssueKind, IssueSeverity>,
+    // per-path overrides of issue_severities, keyed by file glob
+    pub issue_severity_files: Vec<(String, FxHashMap<IssueKind, IssueSeverity>)>,
     pub security_config: SecurityConfig,
+    pub taint_spec: TaintSpec,
     pub root_dir: String,
-    pub hooks: Vec<Box<dyn CustomHook>>,
+    pub hooks: Vec<Arc<dyn CustomHook>>,
     pub ignore_mixed_issues: bool,
     pub add_fixmes: bool,
     pub remove_fixmes: bool,
@@ -40,7 +61,18 @@ pub struct Config {
 pub struct SecurityConfig {
     ignore_files: Vec<String>,
     ignore_sink_files: FxHashMap<String, Vec<String>>,
+    // descriptions of custom sources, used in issue messages
+    custom_sources: FxHashMap<String, String>,
+    pub taint_policy: TaintPolicy,
     pub max_depth: u8,
+    // how many nodes paths from a single source can reach before the search from it stops
+    pub max_nodes_per_source: usize,
+    pub time_budget: Option<Duration>,
+    // whether to emit a TaintAnalysisTruncated issue for every source whose search was cut short
+    pub report_truncated_paths: bool,
+    // ad-hoc sources and sinks for find-paths, connected before the graph is queried
+    pub query_sources: Vec<QueryNode>,
+    pub query_sinks: Vec<QueryNode>,
 }
 
 impl Default for SecurityConfig {
@@ -54,9 +86,26 @@ impl SecurityConfig {
         Self {
             ignore_files: Vec::new(),
             ignore_sink_files: FxHashMap::default(),
+            custom_sources: FxHashMap::default(),
+            taint_policy: TaintPolicy::default(),
             max_depth: 40,
+            max_nodes_per_source: 100_000,
+            time_budget: None,
+            report_truncated_paths: false,
+            query_sources: vec![],
+            query_sinks: vec![],
         }
     }
+
+    pub fn get_source_error_message(&self, source_type: &SourceType) -> String {
+        if let SourceType::Custom(custom_source) = source_type {
+            if let Some(description) = self.custom_sources.get(custom_source) {
+                return description.clone();
+            }
+        }
+
+        source_type.get_error_message()
+    }
 }
 
 impl Config {
@@ -67,13 +116,17 @@ impl Config {
             find_unused_definitions: false,
             ignore_mixed_issues: false,
             allowed_issues: None,
+            directory_allowed_issues: vec![],
             migration_symbols: FxHashMap::default(),
             graph_kind: GraphKind::FunctionBody,
             ignore_files: Vec::new(),
             test_files: Vec::new(),
             ignore_issue_files: FxHashMap::default(),
             ignore_all_issues_in_files: vec![],
+            issue_severities: FxHashMap::default(),
+            issue_severity_files: vec![],
             security_config: SecurityConfig::new(),
+            taint_spec: TaintSpec::get_default(),
             issues_to_fix: FxHashSet::default(),
             hooks: vec![],
             add_fixmes: false,
@@ -82,6 +135,7 @@ impl Config {
             ast_diff: false,
             in_migration: false,
             in_codegen: false,
+            in_language_server: false,
         }
     }
 
@@ -90,68 +144,373 @@ impl Config {
         cwd: &String,
         config_path: &Path,
     ) -> Result<(), Box<dyn Error>> {
-        let json_config = json_config::read_from_file(config_path)?;
+        // every problem is collected so that they can all be fixed in one go
+        let mut errors = vec![];
 
-        self.ignore_files = json_config
-            .ignore_files
-            .into_iter()
-            .map(|v| format!("{}/{}", cwd, v))
-            .collect();
-
-        self.test_files = json_config
-            .test_files
-            .into_iter()
-            .map(|v| format!("{}/{}", cwd, v))
-            .collect();
-
-        self.ignore_issue_files = json_config
-            .ignore_issue_files
-            .iter()
-            .filter(|(k, _)| *k != "*")
-            .map(|(k, v)| {
-                (
-                    IssueKind::from_str_custom(k.as_str(), &self.all_custom_issues).unwrap(),
-                    v.iter().map(|v| format!("{}/{}", cwd, v)).collect(),
-                )
-            })
-            .collect();
+        self.load_config_file(cwd, config_path, None, &mut vec![], &mut errors)?;
 
-        if let Some(v) = json_config.ignore_issue_files.get("*") {
-            self.ignore_all_issues_in_files = v.iter().map(|v| format!("{}/{}", cwd, v)).collect();
+        if !errors.is_empty() {
+            errors.sort();
+            return Err(Box::new(ConfigError { errors }));
         }
 
-        self.allowed_issues = if json_config.allowed_issues.is_empty() {
-            None
+        Ok(())
+    }
+
+    // Globs in a config file are relative to base_dir. Settings from a config file
+    // loaded for a directory only apply to files inside that directory, and fall
+    // back to the settings of the enclosing config.
+    fn load_config_file(
+        &mut self,
+        base_dir: &str,
+        config_path: &Path,
+        directory: Option<&str>,
+        loading_paths: &mut Vec<PathBuf>,
+        errors: &mut Vec<String>,
+    ) -> Result<(), Box<dyn Error>> {
+        let canonical_path = config_path
+            .canonicalize()
+            .map_err(|error| format!("{}: {}", config_path.display(), error))?;
+
+        // extending an enclosing config is a no-op, since its settings are inherited anyway
+        if loading_paths.contains(&canonical_path) {
+            return Ok(());
+        }
+
+        let json_config = json_config::read_from_file(config_path)
+            .map_err(|error| format!("{}: {}", config_path.display(), error))?;
+
+        loading_paths.push(canonical_path);
+
+        // errors from the top-level config file aren't prefixed, since it's named already
+        let error_prefix = if loading_paths.len() > 1 {
+            format!("{}: ", config_path.display())
         } else {
-            Some(
-                json_config
-                    .allowed_issues
-                    .into_iter()
-                    .map(|s| {
-                        IssueKind::from_str_custom(s.as_str(), &self.all_custom_issues).unwrap()
-                    })
-                    .collect::<FxHashSet<_>>(),
-            )
+            "".to_string()
         };
 
-        self.security_config.ignore_files = json_config
+        if let Some(extends) = &json_config.extends {
+            let parent_path = config_path.parent().unwrap().join(extends);
+
+            if parent_path.exists() {
+                // globs in the parent config are relative to the directory it lives in
+                let parent_base_dir = normalize_dir(parent_path.parent().unwrap());
+
+                self.load_config_file(
+                    &parent_base_dir,
+                    &parent_path,
+                    directory,
+                    loading_paths,
+                    errors,
+                )?;
+            } else {
+                errors.push(format!(
+                    "{}extends: {} does not exist",
+                    error_prefix,
+                    parent_path.display()
+                ));
+            }
+        }
+
+        let first_error = errors.len();
+
+        for (i, v) in json_config.ignore_files.iter().enumerate() {
+            validate_glob(v, format!("ignore_files[{}]", i), errors);
+        }
+
+        self.ignore_files.extend(
+            json_config
+                .ignore_files
+                .iter()
+                .map(|v| format!("{}/{}", base_dir, v)),
+        );
+
+        for (i, v) in json_config.test_files.iter().enumerate() {
+            validate_glob(v, format!("test_files[{}]", i), errors);
+        }
+
+        self.test_files.extend(
+            json_config
+                .test_files
+                .iter()
+                .map(|v| format!("{}/{}", base_dir, v)),
+        );
+
+        for (k, v) in &json_config.ignore_issue_files {
+            for (i, file_glob) in v.iter().enumerate() {
+                validate_glob(
+                    file_glob,
+                    format!("ignore_issue_files.{}[{}]", k, i),
+                    errors,
+                );
+            }
+
+            let file_globs = v.iter().map(|v| format!("{}/{}", base_dir, v));
+
+            if k == "*" {
+                self.ignore_all_issues_in_files.extend(file_globs);
+            } else if let Some(issue_kind) =
+                self.get_issue_kind(k, format!("ignore_issue_files.{}", k), errors)
+            {
+                self.ignore_issue_files
+                    .entry(issue_kind)
+                    .or_default()
+                    .extend(file_globs);
+            }
+        }
+
+        if !json_config.allowed_issues.is_empty() {
+            let allowed_issues = json_config
+                .allowed_issues
+                .iter()
+                .enumerate()
+                .filter_map(|(i, s)| {
+                    self.get_issue_kind(s, format!("allowed_issues[{}]", i), errors)
+                })
+                .collect::<FxHashSet<_>>();
+
+            if let Some(directory) = directory {
+                self.directory_allowed_issues
+                    .insert(0, (directory.to_string(), allowed_issues));
+            } else {
+                self.allowed_issues = Some(allowed_issues);
+            }
+        }
+
+        let issue_severities = json_config
+            .issue_severity
+            .iter()
+            .filter_map(|(k, v)| {
+                self.get_issue_kind(k, format!("issue_severity.{}", k), errors)
+                    .map(|issue_kind| (issue_kind, *v))
+            })
+            .collect::<FxHashMap<_, _>>();
+
+        if let Some(directory) = directory {
+            if !issue_severities.is_empty() {
+                self.issue_severity_files
+                    .insert(0, (format!("{}/**", directory), issue_severities));
+            }
+        } else {
+            self.issue_severities.extend(issue_severities);
+        }
+
+        // entries are checked in order, so ones from more specific configs go first
+        for (path, severities) in &json_config.issue_severity_files {
+            validate_glob(path, format!("issue_severity_files[\"{}\"]", path), errors);
+
+            let severities = severities
+                .iter()
+                .filter_map(|(k, v)| {
+                    self.get_issue_kind(
+                        k,
+                        format!("issue_severity_files[\"{}\"].{}", path, k),
+                        errors,
+                    )
+                    .map(|issue_kind| (issue_kind, *v))
+                })
+                .collect();
+
+            self.issue_severity_files
+                .insert(0, (format!("{}/{}", base_dir, path), severities));
+        }
+
+        for (i, v) in json_config
             .security_analysis
             .ignore_files
-            .into_iter()
-            .map(|v| format!("{}/{}", cwd, v))
-            .collect();
-        self.security_config.ignore_sink_files = json_config
-            .security_analysis
-            .ignore_sink_files
-            .into_iter()
-            .map(|(k, v)| (k, v.into_iter().map(|v| format!("{}/{}", cwd, v)).collect()))
-            .collect();
+            .iter()
+            .enumerate()
+        {
+            validate_glob(v, format!("security_analysis.ignore_files[{}]", i), errors);
+        }
+
+        self.security_config.ignore_files.extend(
+            json_config
+                .security_analysis
+                .ignore_files
+                .iter()
+                .map(|v| format!("{}/{}", base_dir, v)),
+        );
+
+        // custom sources are declared once for the whole codebase
+        if directory.is_some() && !json_config.security_analysis.custom_sources.is_empty() {
+            errors.push(
+                "security_analysis.custom_sources: can only be set in the top-level config"
+                    .to_string(),
+            );
+        }
+
+        self.security_config.custom_sources.extend(
+            json_config
+                .security_analysis
+                .custom_sources
+                .iter()
+                .map(|(k, v)| (k.clone(), v.clone())),
+        );
+
+        // the policy decides which flows are reported at all, so it can't vary by directory
+        if directory.is_some() && json_config.security_analysis.taint_policy.is_some() {
+            errors.push(
+                "security_analysis.taint_policy: can only be set in the top-level config"
+                    .to_string(),
+            );
+        }
+
+        if let Some(taint_policy) = &json_config.security_analysis.taint_policy {
+            if taint_policy.ignore_default_flows {
+                self.security_config.taint_policy = TaintPolicy::empty();
+            }
+
+            for (i, flow) in taint_policy.forbidden_flows.iter().enumerate() {
+                if let Some((source_type, sink_type)) = parse_taint_flow(
+                    flow,
+                    &self.security_config.custom_sources,
+                    &format!("security_analysis.taint_policy.forbidden_flows[{}]", i),
+                    errors,
+                ) {
+                    self.security_config
+                        .taint_policy
+                        .forbid(source_type, sink_type);
+                }
+            }
+
+            for (i, flow) in taint_policy.allowed_flows.iter().enumerate() {
+                if let Some((source_type, sink_type)) = parse_taint_flow(
+                    flow,
+                    &self.security_config.custom_sources,
+                    &format!("security_analysis.taint_policy.allowed_flows[{}]", i),
+                    errors,
+                ) {
+                    self.security_config
+                        .taint_policy
+                        .allow(&source_type, &sink_type);
+                }
+            }
+        }
+
+        for (k, v) in &json_config.security_analysis.ignore_sink_files {
+            let json_path = format!("security_analysis.ignore_sink_files[\"{}\"]", k);
+
+            if let Some((source_type, sink_type)) =
+                parse_taint_flow(k, &self.security_config.custom_sources, &json_path, errors)
+            {
+                if !self
+                    .security_config
+                    .taint_policy
+                    .forbids(&source_type, &sink_type)
+                {
+                    errors.push(format!(
+                        "{}: data from {} is never reported when it reaches {}",
+                        json_path, source_type, sink_type
+                    ));
+                }
+            }
+
+            for (i, file_glob) in v.iter().enumerate() {
+                validate_glob(file_glob, format!("{}[{}]", json_path, i), errors);
+            }
+
+            self.security_config
+                .ignore_sink_files
+                .entry(k.clone())
+                .or_default()
+                .extend(v.iter().map(|v| format!("{}/{}", base_dir, v)));
+        }
+
+        // taint specs describe functions wherever they're called, so can't be scoped to a directory
+        if directory.is_some() && !json_config.security_analysis.taint_specs.is_empty() {
+            errors.push(
+                "security_analysis.taint_specs: can only be set in the top-level config"
+                    .to_string(),
+            );
+        }
+
+        for error in errors.iter_mut().skip(first_error) {
+            *error = format!("{}{}", error_prefix, error);
+        }
+
+        for (i, v) in json_config.security_analysis.taint_specs.iter().enumerate() {
+            let taint_spec_path = config_path.parent().unwrap().join(v);
+
+            match taint_spec::read_from_file(&taint_spec_path) {
+                Ok((taint_spec, taint_spec_errors)) => {
+                    self.taint_spec.extend(taint_spec);
+
+                    errors.extend(taint_spec_errors.into_iter().map(|error| {
+                        format!("{}{}: {}", error_prefix, taint_spec_path.display(), error)
+                    }));
+                }
+                Err(error) => {
+                    errors.push(format!(
+                        "{}security_analysis.taint_specs[{}]: could not load {} ({})",
+                        error_prefix,
+                        i,
+                        taint_spec_path.display(),
+                        error
+                    ));
+                }
+            }
+        }
+
+        for (i, v) in json_config.directory_configs.iter().enumerate() {
+            let directory = format!("{}/{}", base_dir, v.trim_end_matches('/'));
+            let directory_config_path = Path::new(&directory).join("hakana.json");
+
+            if directory_config_path.exists() {
+                self.load_config_file(
+                    &directory,
+                    &directory_config_path,
+                    Some(&directory),
+                    loading_paths,
+                    errors,
+                )?;
+            } else {
+                errors.push(format!(
+                    "{}directory_configs[{}]: {} does not exist",
+                    error_prefix,
+                    i,
+                    directory_config_path.display()
+                ));
+            }
+        }
+
+        loading_paths.pop();
 
         Ok(())
     }
 
-    pub fn can_add_issue(&self, issue: &Issue) -> bool {
-        if let Some(issue_filter) = &self.allowed_issues {
+    fn get_issue_kind(
+        &self,
+        name: &str,
+        json_path: String,
+        errors: &mut Vec<String>,
+    ) -> Option<IssueKind> {
+        match IssueKind::from_str_custom(name, &self.all_custom_issues) {
+            Ok(issue_kind) => Some(issue_kind),
+            Err(_) => {
+                let suggestion = get_closest_match(
+                    name,
+                    IssueKind::VARIANTS
+                        .iter()
+                        .copied()
+                        .filter(|v| *v != "CustomIssue")
+                        .chain(self.all_custom_issues.iter().map(|v| v.as_str())),
+                );
+
+                errors.push(format!(
+                    "{}: unknown issue {}{}",
+                    json_path,
+                    name,
+                    format_suggestion(suggestion)
+                ));
+
+                None
+            }
+        }
+    }
+
+    pub fn can_add_issue(&self, issue: &Issue, file: &str) -> bool {
+        if let Some(issue_filter) = self.get_allowed_issues(file) {
             if !issue_filter.contains(&issue.kind) {
                 return false;
             }
@@ -160,6 +519,22 @@ impl Config {
         true
     }
 
+    pub fn get_allowed_issues(&self, file: &str) -> Option<&FxHashSet<IssueKind>> {
+        for (directory, allowed_issues) in &self.directory_allowed_issues {
+            if file.starts_with(directory) && file[directory.len()..].starts_with('/') {
+                return Some(allowed_issues);
+            }
+        }
+
+        self.allowed_issues.as_ref()
+    }
+
+    // used when the command line decides which issues to show, regardless of config
+    pub fn override_allowed_issues(&mut self, allowed_issues: Option<FxHashSet<IssueKind>>) {
+        self.allowed_issues = allowed_issues;
+        self.directory_allowed_issues = vec![];
+    }
+
     pub fn allow_issues_in_file(&self, file: &str) -> bool {
         for ignore_file_path in &self.ignore_all_issues_in_files {
             if glob::Pattern::new(ignore_file_path).unwrap().matches(file) {
@@ -171,6 +546,10 @@ impl Config {
     }
 
     pub fn allow_issue_kind_in_file(&self, issue_kind: &IssueKind, file: &str) -> bool {
+        if self.get_issue_severity(issue_kind, file) == IssueSeverity::Off {
+            return false;
+        }
+
         if let Some(issue_entries) = self.ignore_issue_files.get(issue_kind) {
             for ignore_file_path in issue_entries {
                 if glob::Pattern::new(ignore_file_path).unwrap().matches(file) {
@@ -182,6 +561,41 @@ impl Config {
         true
     }
 
+    pub fn get_issue_severity(&self, issue_kind: &IssueKind, file: &str) -> IssueSeverity {
+        for (severity_file_path, severities) in &self.issue_severity_files {
+            if let Some(severity) = severities.get(issue_kind) {
+                if glob::Pattern::new(severity_file_path)
+                    .unwrap()
+                    .matches(file)
+                {
+                    return *severity;
+                }
+            }
+        }
+
+        self.issue_severities
+            .get(issue_kind)
+            .copied()
+            .unwrap_or_default()
+    }
+
+    // a hash of the settings that shape each symbol's dataflow graph, so that cached graphs
+    // are only reused while those settings are unchanged
+    pub fn get_dataflow_graph_config_hash(&self) -> u64 {
+        let mut hasher = FxHasher::default();
+
+        format!(
+            "{:?}{:?}{:?}{:?}",
+            self.graph_kind,
+            self.taint_spec,
+            self.security_config.custom_sources,
+            self.security_config.ignore_files
+        )
+        .hash(&mut hasher);
+
+        hasher.finish()
+    }
+
     pub fn allow_taints_in_file(&self, file: &str) -> bool {
         for ignore_file_path in &self.security_config.ignore_files {
             if glob::Pattern::new(ignore_file_path).unwrap().matches(file) {
@@ -199,7 +613,7 @@ impl Config {
         node: &TaintedNode,
         interner: &Interner,
     ) -> bool {
-        let str_type = source_type.to_string() + " -> " + &sink_type.to_string();
+        let str_type = get_taint_flow_key(source_type, sink_type);
 
         if let Some(issue_entries) = self.security_config.ignore_sink_files.get(&str_type) {
             let ignore_patterns = issue_entries
@@ -229,3 +643,450 @@ impl Config {
         true
     }
 }
+
+#[derive(Debug)]
+pub struct ConfigError {
+    pub errors: Vec<String>,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.errors.join("\n"))
+    }
+}
+
+impl Error for ConfigError {}
+
+// resolves . and .. without touching the filesystem, so globs match the paths files are analyzed under
+fn normalize_dir(path: &Path) -> String {
+    let mut normalized = PathBuf::new();
+
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir
+                if matches!(normalized.components().last(), Some(Component::Normal(_))) =>
+            {
+                normalized.pop();
+            }
+            _ => normalized.push(component),
+        }
+    }
+
+    if normalized.as_os_str().is_empty() {
+        ".".to_string()
+    } else {
+        normalized.to_string_lossy().to_string()
+    }
+}
+
+fn validate_glob(pattern: &str, json_path: String, errors: &mut Vec<String>) {
+    if let Err(error) = glob::Pattern::new(pattern) {
+        errors.push(format!(
+            "{}: invalid glob {} ({})",
+            json_path, pattern, error
+        ));
+    }
+}
+
+// flows look like "UriRequestHeader -> HtmlTag" or "UserPII -> Custom:Analytics"
+fn parse_taint_flow(
+    flow: &str,
+    custom_sources: &FxHashMap<String, String>,
+    json_path: &str,
+    errors: &mut Vec<String>,
+) -> Option<(SourceType, SinkType)> {
+    let (source_str, sink_str) = if let Some(parts) = flow.split_once(" -> ") {
+        parts
+    } else {
+        errors.push(format!(
+            "{}: expected a flow of the form \"Source -> Sink\"",
+            json_path
+        ));
+        return None;
+    };
+
+    let source_type = if let Some(custom_source) = source_str.strip_prefix("Custom:") {
+        if custom_sources.contains_key(custom_source) {
+            Some(SourceType::Custom(custom_source.to_string()))
+        } else {
+            errors.push(format!(
+                "{}: custom source {} is not declared in security_analysis.custom_sources{}",
+                json_path,
+                custom_source,
+                format_suggestion(get_closest_match(
+                    custom_source,
+                    custom_sources.keys().map(|k| k.as_str())
+                ))
+            ));
+            None
+        }
+    } else if let (Ok(source_type), true) =
+        (SourceType::from_str(source_str), source_str != "Custom")
+    {
+        Some(source_type)
+    } else {
+        errors.push(format!(
+            "{}: unknown taint source {}{}",
+            json_path,
+            source_str,
+            format_suggestion(get_closest_match(
+                source_str,
+                SourceType::VARIANTS
+                    .iter()
+                    .copied()
+                    .filter(|v| *v != "Custom")
+            ))
+        ));
+        None
+    };
+
+    let sink_type = if let Some(custom_sink) = sink_str.strip_prefix("Custom:") {
+        Ok(SinkType::Custom(custom_sink.to_string()))
+    } else {
+        SinkType::from_str(sink_str).map_err(|_| ())
+    };
+
+    if sink_type.is_err() || sink_str == "Custom" {
+        errors.push(format!(
+            "{}: unknown taint sink {}{}",
+            json_path,
+            sink_str,
+            format_suggestion(get_closest_match(
+                sink_str,
+                SinkType::VARIANTS
+                    .iter()
+                    .copied()
+                    .filter(|v| *v != "Custom")
+            ))
+        ));
+
+        return None;
+    }
+
+    Some((source_type?, sink_type.ok()?))
+}
+
+fn get_taint_flow_key(source_type: &SourceType, sink_type: &SinkType) -> String {
+    let source_str = match source_type {
+        SourceType::Custom(custom_source) => format!("Custom:{}", custom_source),
+        _ => source_type.to_string(),
+    };
+
+    match sink_type {
+        SinkType::Custom(custom_sink) => format!("{} -> Custom:{}", source_str, custom_sink),
+        _ => format!("{} -> {}", source_str, sink_type),
+    }
+}
+
+fn format_suggestion(suggestion: Option<&str>) -> String {
+    if let Some(suggestion) = suggestion {
+        format!(" (did you mean {}?)", suggestion)
+    } else {
+        "".to_string()
+    }
+}
+
+// only suggests candidates that are a plausible typo of the given name
+fn get_closest_match<'a>(name: &str, candidates: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let name = name.to_lowercase();
+
+    candidates
+        .map(|candidate| {
+            (
+                get_edit_distance(&name, &candidate.to_lowercase()),
+                candidate,
+            )
+        })
+        .filter(|(distance, candidate)| *distance <= std::cmp::max(2, candidate.len() / 3))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+fn get_edit_distance(a: &str, b: &str) -> usize {
+    let b_chars = b.chars().collect::<Vec<_>>();
+    let mut previous_row = (0..=b_chars.len()).collect::<Vec<_>>();
+
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current_row = vec![i + 1];
+
+        for (j, b_char) in b_chars.iter().enumerate() {
+            current_row.push(
+                (previous_row[j] + usize::from(a_char != *b_char))
+                    .min(previous_row[j + 1] + 1)
+                    .min(current_row[j] + 1),
+            );
+        }
+
+        previous_row = current_row;
+    }
+
+    previous_row[b_chars.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Config, ConfigError};
+    use hakana_reflection_info::issue::{IssueKind, IssueSeverity};
+    use rustc_hash::FxHashSet;
+    use std::{fs, path::Path};
+
+    // writes the given files to a fresh directory and loads its hakana.json
+    fn load_config(name: &str, files: &[(&str, &str)]) -> (String, Result<Config, Vec<String>>) {
+        let dir = std::env::temp_dir().join(format!("hakana_config_test_{}", name));
+
+        if dir.exists() {
+            fs::remove_dir_all(&dir).unwrap();
+        }
+
+        for (path, contents) in files {
+            let path = dir.join(path);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, contents).unwrap();
+        }
+
+        let dir = dir.to_str().unwrap().to_string();
+        let mut config = Config::new(dir.clone(), FxHashSet::default());
+
+        let result = config
+            .update_from_file(&dir, &Path::new(&dir).join("hakana.json"))
+            .map(|_| config)
+            .map_err(|error| {
+                if let Some(config_error) = error.downcast_ref::<ConfigError>() {
+                    config_error.errors.clone()
+                } else {
+                    vec![error.to_string()]
+                }
+            });
+
+        (dir, result)
+    }
+
+    #[test]
+    fn issue_severities() {
+        let (dir, config) = load_config(
+            "severities",
+            &[(
+                "hakana.json",
+                r#"{
+                    "issue_severity": {
+                        "UnusedAssignment": "warning",
+                        "InvalidReturnStatement": "off"
+                    },
+                    "issue_severity_files": {
+                        "tests/*": { "UnusedAssignment": "info" }
+                    }
+                }"#,
+            )],
+        );
+        let config = config.unwrap();
+
+        let src_file = format!("{}/src/a.hack", dir);
+        let test_file = format!("{}/tests/a.hack", dir);
+
+        assert_eq!(
+            config.get_issue_severity(&IssueKind::UnusedAssignment, &src_file),
+            IssueSeverity::Warning
+        );
+        assert_eq!(
+            config.get_issue_severity(&IssueKind::UnusedAssignment, &test_file),
+            IssueSeverity::Info
+        );
+        assert_eq!(
+            config.get_issue_severity(&IssueKind::UnusedParameter, &src_file),
+            IssueSeverity::Error
+        );
+        assert_eq!(
+            config.get_issue_severity(&IssueKind::InvalidReturnStatement, &test_file),
+            IssueSeverity::Off
+        );
+
+        assert!(config.allow_issue_kind_in_file(&IssueKind::UnusedAssignment, &test_file));
+        assert!(!config.allow_issue_kind_in_file(&IssueKind::InvalidReturnStatement, &src_file));
+    }
+
+    #[test]
+    fn validation_errors() {
+        let (_, config) = load_config(
+            "validation",
+            &[(
+                "hakana.json",
+                r#"{
+                    "ignore_files": ["src/[a"],
+                    "allowed_issues": ["InvalidReturnStatment"],
+                    "issue_severity": { "UnusedAssignmnt": "warning" },
+                    "security_analysis": {
+                        "taint_policy": {
+                            "forbidden_flows": ["UriRequestHeadr -> HtmlTag", "RawUserData"]
+                        }
+                    }
+                }"#,
+            )],
+        );
+        let errors = config.unwrap_err();
+
+        assert_eq!(errors.len(), 5, "{:?}", errors);
+        assert_eq!(
+            errors[0],
+            "allowed_issues[0]: unknown issue InvalidReturnStatment (did you mean InvalidReturnStatement?)"
+        );
+        assert!(
+            errors[1].starts_with("ignore_files[0]: invalid glob src/[a ("),
+            "{}",
+            errors[1]
+        );
+        assert_eq!(
+            errors[2],
+            "issue_severity.UnusedAssignmnt: unknown issue UnusedAssignmnt (did you mean UnusedAssignment?)"
+        );
+        assert_eq!(
+            errors[3],
+            "security_analysis.taint_policy.forbidden_flows[0]: unknown taint source UriRequestHeadr (did you mean UriRequestHeader?)"
+        );
+        assert_eq!(
+            errors[4],
+            "security_analysis.taint_policy.forbidden_flows[1]: expected a flow of the form \"Source -> Sink\""
+        );
+    }
+
+    #[test]
+    fn unknown_keys_are_rejected() {
+        let (_, config) = load_config(
+            "unknown_keys",
+            &[("hakana.json", r#"{ "ignore_file": ["src/*"] }"#)],
+        );
+        let errors = config.unwrap_err();
+
+        assert!(
+            errors[0].contains("unknown field `ignore_file`"),
+            "{}",
+            errors[0]
+        );
+    }
+
+    #[test]
+    fn extends_and_directory_configs() {
+        let (dir, config) = load_config(
+            "extends",
+            &[
+                (
+                    "hakana.json",
+                    r#"{
+                        "extends": "shared/base.json",
+                        "directory_configs": ["legacy"],
+                        "issue_severity": { "UnusedAssignment": "info" }
+                    }"#,
+                ),
+                (
+                    "shared/base.json",
+                    r#"{
+                        "ignore_files": ["generated/*"],
+                        "issue_severity": {
+                            "UnusedAssignment": "warning",
+                            "UnusedParameter": "warning"
+                        }
+                    }"#,
+                ),
+                (
+                    "legacy/hakana.json",
+                    r#"{
+                        "allowed_issues": ["UnusedAssignment"],
+                        "issue_severity": { "UnusedAssignment": "off" }
+                    }"#,
+                ),
+            ],
+        );
+        let config = config.unwrap();
+
+        // globs in an extended config are relative to that config
+        assert_eq!(
+            config.ignore_files,
+            vec![format!("{}/shared/generated/*", dir)]
+        );
+
+        let src_file = format!("{}/src/a.hack", dir);
+        let legacy_file = format!("{}/legacy/a.hack", dir);
+
+        // the extending config overrides the config it extends
+        assert_eq!(
+            config.get_issue_severity(&IssueKind::UnusedAssignment, &src_file),
+            IssueSeverity::Info
+        );
+        assert_eq!(
+            config.get_issue_severity(&IssueKind::UnusedParameter, &src_file),
+            IssueSeverity::Warning
+        );
+
+        // directory configs only apply inside their directory
+        assert_eq!(
+            config.get_issue_severity(&IssueKind::UnusedAssignment, &legacy_file),
+            IssueSeverity::Off
+        );
+        assert_eq!(
+            config.get_issue_severity(&IssueKind::UnusedParameter, &legacy_file),
+            IssueSeverity::Warning
+        );
+        assert_eq!(
+            config.get_allowed_issues(&legacy_file),
+            Some(&FxHashSet::from_iter([IssueKind::UnusedAssignment]))
+        );
+        assert_eq!(config.get_allowed_issues(&src_file), None);
+    }
+
+    #[test]
+    fn directory_configs_reject_codebase_settings() {
+        let (dir, config) = load_config(
+            "directory_rejections",
+            &[
+                (
+                    "hakana.json",
+                    r#"{
+                        "extends": "missing.json",
+                        "directory_configs": ["legacy", "missing"]
+                    }"#,
+                ),
+                (
+                    "legacy/hakana.json",
+                    r#"{
+                        "security_analysis": {
+                            "custom_sources": { "Secret": "a secret" },
+                            "taint_policy": { "forbidden_flows": [] },
+                            "taint_specs": ["spec.json"]
+                        }
+                    }"#,
+                ),
+                ("legacy/spec.json", r#"{ "functions": {} }"#),
+            ],
+        );
+        let errors = config.unwrap_err();
+
+        let legacy_prefix = format!("{}/legacy/hakana.json: ", dir);
+
+        for expected_error in [
+            format!("extends: {}/missing.json does not exist", dir),
+            format!(
+                "directory_configs[1]: {}/missing/hakana.json does not exist",
+                dir
+            ),
+            format!(
+                "{}security_analysis.custom_sources: can only be set in the top-level config",
+                legacy_prefix
+            ),
+            format!(
+                "{}security_analysis.taint_policy: can only be set in the top-level config",
+                legacy_prefix
+            ),
+            format!(
+                "{}security_analysis.taint_specs: can only be set in the top-level config",
+                legacy_prefix
+            ),
+        ] {
+            assert!(
+                errors.contains(&expected_error),
+                "{} not in {:?}",
+                expected_error,
+                errors
+            );
+        }
+    }
+}
@@ -0,0 +1,217 @@
+use hakana_reflection_info::{
+    code_location::HPos, codebase_info::CodebaseInfo, data_flow::node::DataFlowNodeId,
+    function_context::FunctionLikeIdentifier,
+};
+use hakana_str::{Interner, StrId};
+
+// A source or sink given to find-paths on the command line, e.g. Foo::getToken:return
+#[derive(Clone, Debug)]
+pub struct QueryNode {
+    // a function name, or Class::method, or Class::$property
+    pub symbol: String,
+    pub kind: QueryNodeKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum QueryNodeKind {
+    Return,
+    // the argument offset, or None for every argument
+    Param(Option<usize>),
+    Property,
+}
+
+impl QueryNode {
+    // parses <symbol>[:return|:param N|:property], defaulting to the return value
+    pub fn parse_source(str: &str) -> Result<Self, String> {
+        let (symbol, kind) = split_kind(str);
+
+        let kind = match kind {
+            None | Some("return") => QueryNodeKind::Return,
+            Some("property") => QueryNodeKind::Property,
+            Some(kind) => parse_param_kind(kind).ok_or_else(|| {
+                format!(
+                    "Invalid source {} — expected <symbol>[:return|:param N|:property]",
+                    str
+                )
+            })?,
+        };
+
+        Self::new(symbol, kind, str)
+    }
+
+    // parses <symbol>:param N, where N is an argument offset or * for every argument
+    pub fn parse_sink(str: &str) -> Result<Self, String> {
+        let (symbol, kind) = split_kind(str);
+
+        let kind = kind
+            .and_then(parse_param_kind)
+            .ok_or_else(|| format!("Invalid sink {} — expected <symbol>:param N", str))?;
+
+        Self::new(symbol, kind, str)
+    }
+
+    fn new(symbol: &str, kind: QueryNodeKind, str: &str) -> Result<Self, String> {
+        let symbol = symbol.trim_start_matches('\\');
+
+        if symbol.is_empty() || (kind == QueryNodeKind::Property && !symbol.contains("::")) {
+            return Err(format!("Invalid symbol in {}", str));
+        }
+
+        Ok(Self {
+            symbol: symbol.to_string(),
+            kind,
+        })
+    }
+
+    // the graph nodes this refers to, and where they're defined
+    pub fn get_node_ids(
+        &self,
+        codebase: &CodebaseInfo,
+        interner: &Interner,
+    ) -> Option<Vec<(DataFlowNodeId, HPos)>> {
+        let (first, second) = if let Some((class_name, member_name)) = self.symbol.split_once("::")
+        {
+            (
+                interner.get(class_name)?,
+                interner.get(member_name.trim_start_matches('$'))?,
+            )
+        } else {
+            (interner.get(&self.symbol)?, StrId::EMPTY)
+        };
+
+        if let QueryNodeKind::Property = self.kind {
+            let property_info = codebase
+                .classlike_infos
+                .get(&first)?
+                .properties
+                .get(&second)?;
+
+            return Some(vec![(
+                DataFlowNodeId::Property(first, second),
+                property_info.pos?,
+            )]);
+        }
+
+        let functionlike_info = codebase.functionlike_infos.get(&(first, second))?;
+
+        let functionlike_id = if second == StrId::EMPTY {
+            FunctionLikeIdentifier::Function(first)
+        } else {
+            FunctionLikeIdentifier::Method(first, second)
+        };
+
+        Some(match self.kind {
+            QueryNodeKind::Return => vec![(
+                DataFlowNodeId::CallTo(functionlike_id),
+                functionlike_info.def_location,
+            )],
+            QueryNodeKind::Param(Some(offset)) => vec![(
+                DataFlowNodeId::FunctionLikeArg(functionlike_id, offset as u8),
+                functionlike_info.params.get(offset)?.location,
+            )],
+            QueryNodeKind::Param(None) => functionlike_info
+                .params
+                .iter()
+                .enumerate()
+                .map(|(offset, param)| {
+                    (
+                        DataFlowNodeId::FunctionLikeArg(functionlike_id, offset as u8),
+                        param.location,
+                    )
+                })
+                .collect(),
+            QueryNodeKind::Property => unreachable!("property nodes are returned above"),
+        })
+    }
+}
+
+// splits Foo::bar:param 0 into Foo::bar and param 0
+fn split_kind(str: &str) -> (&str, Option<&str>) {
+    if let Some((symbol, kind)) = str.rsplit_once(':') {
+        if !symbol.ends_with(':') {
+            return (symbol, Some(kind.trim()));
+        }
+    }
+
+    (str, None)
+}
+
+fn parse_param_kind(kind: &str) -> Option<QueryNodeKind> {
+    let offset = kind.strip_prefix("param")?.trim();
+
+    if offset == "*" {
+        Some(QueryNodeKind::Param(None))
+    } else {
+        offset
+            .parse::<usize>()
+            .ok()
+            .map(|offset| QueryNodeKind::Param(Some(offset)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{QueryNode, QueryNodeKind};
+
+    #[test]
+    fn parses_source_kinds() {
+        let cases = [
+            ("get_token", "get_token", QueryNodeKind::Return),
+            (
+                "Foo::getToken:return",
+                "Foo::getToken",
+                QueryNodeKind::Return,
+            ),
+            ("\\Foo::getToken", "Foo::getToken", QueryNodeKind::Return),
+            ("send:param 1", "send", QueryNodeKind::Param(Some(1))),
+            ("Foo::$bar:property", "Foo::$bar", QueryNodeKind::Property),
+        ];
+
+        for (str, symbol, kind) in cases {
+            let node = QueryNode::parse_source(str).unwrap();
+            assert_eq!(node.symbol, symbol, "{}", str);
+            assert_eq!(node.kind, kind, "{}", str);
+        }
+    }
+
+    #[test]
+    fn parses_sink_kinds() {
+        let node = QueryNode::parse_sink("Logger::log:param 0").unwrap();
+        assert_eq!(node.symbol, "Logger::log");
+        assert_eq!(node.kind, QueryNodeKind::Param(Some(0)));
+
+        let node = QueryNode::parse_sink("Logger::log:param *").unwrap();
+        assert_eq!(node.kind, QueryNodeKind::Param(None));
+    }
+
+    #[test]
+    fn rejects_malformed_sources() {
+        for str in [
+            "",
+            "\\",
+            ":return",
+            "foo:bogus",
+            "foo:param",
+            "foo:param x",
+            "foo:param -1",
+            "foo:property",
+        ] {
+            assert!(QueryNode::parse_source(str).is_err(), "{}", str);
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_sinks() {
+        for str in [
+            "",
+            "Logger::log",
+            "Logger::log:return",
+            "Logger::log:property",
+            "Logger::log:param",
+            "Logger::log:param x",
+            ":param 0",
+        ] {
+            assert!(QueryNode::parse_sink(str).is_err(), "{}", str);
+        }
+    }
+}
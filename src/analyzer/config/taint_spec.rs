@@ -0,0 +1,192 @@
+use std::{error::Error, fs::File, io::BufReader, path::Path};
+
+use hakana_reflection_info::{
+    function_context::FunctionLikeIdentifier,
+    taint::{SinkType, SourceType},
+};
+use hakana_str::Interner;
+use rustc_hash::FxHashMap;
+use serde::Deserialize;
+
+// The sinks, sources and sanitizers of HHVM builtins. Projects can add their own
+// for vendor code via security_analysis.taint_specs in hakana.json.
+const DEFAULT_TAINT_SPEC: &str = include_str!("default_taint_spec.json");
+
+#[derive(Clone, Debug, Default)]
+pub struct TaintSpec {
+    // keyed by function name, or Class::method for methods
+    functions: FxHashMap<String, FunctionTaintSpec>,
+}
+
+#[derive(Clone, Deserialize, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct FunctionTaintSpec {
+    // taints on the function's return value
+    #[serde(default)]
+    pub sources: Vec<SourceType>,
+    // keyed by argument offset, or "*" for every argument
+    #[serde(default)]
+    pub sinks: FxHashMap<String, Vec<SinkType>>,
+    // taints removed from data passing through each argument, keyed like sinks
+    #[serde(default)]
+    pub sanitizes: FxHashMap<String, Vec<SinkType>>,
+    // taints added to data passing through each argument, keyed like sinks
+    #[serde(default)]
+    pub adds_taints: FxHashMap<String, Vec<SinkType>>,
+    // the arguments whose data ends up in the return value, overriding the default
+    // assumption for builtins that every argument does
+    #[serde(default)]
+    pub passthrough: Option<Vec<usize>>,
+}
+
+#[derive(Deserialize, Debug)]
+#[serde(deny_unknown_fields)]
+struct JsonTaintSpec {
+    functions: FxHashMap<String, FunctionTaintSpec>,
+}
+
+impl TaintSpec {
+    pub fn get_default() -> Self {
+        let json_spec: JsonTaintSpec =
+            serde_json::from_str(DEFAULT_TAINT_SPEC).expect("default taint spec should be valid");
+
+        Self {
+            functions: json_spec.functions,
+        }
+    }
+
+    pub fn get(
+        &self,
+        functionlike_id: &FunctionLikeIdentifier,
+        interner: &Interner,
+    ) -> Option<&FunctionTaintSpec> {
+        if self.functions.is_empty() {
+            return None;
+        }
+
+        match functionlike_id {
+            FunctionLikeIdentifier::Function(function_name) => {
+                self.functions.get(interner.lookup(function_name))
+            }
+            FunctionLikeIdentifier::Method(fq_class, method_name) => self.functions.get(&format!(
+                "{}::{}",
+                interner.lookup(fq_class),
+                interner.lookup(method_name)
+            )),
+            FunctionLikeIdentifier::Closure(..) => None,
+        }
+    }
+
+    // entries for functions that are already specified are merged with the existing ones
+    pub fn extend(&mut self, other: TaintSpec) {
+        for (function_name, function_spec) in other.functions {
+            let existing_spec = self.functions.entry(function_name).or_default();
+
+            existing_spec.sources.extend(function_spec.sources);
+
+            for (arg, sinks) in function_spec.sinks {
+                existing_spec.sinks.entry(arg).or_default().extend(sinks);
+            }
+
+            for (arg, sinks) in function_spec.sanitizes {
+                existing_spec
+                    .sanitizes
+                    .entry(arg)
+                    .or_default()
+                    .extend(sinks);
+            }
+
+            for (arg, sinks) in function_spec.adds_taints {
+                existing_spec
+                    .adds_taints
+                    .entry(arg)
+                    .or_default()
+                    .extend(sinks);
+            }
+
+            if function_spec.passthrough.is_some() {
+                existing_spec.passthrough = function_spec.passthrough;
+            }
+        }
+    }
+}
+
+impl FunctionTaintSpec {
+    pub fn get_argument_sinks(&self, arg_offset: usize) -> Vec<SinkType> {
+        get_for_argument(&self.sinks, arg_offset)
+    }
+
+    // the (added, removed) taints for data passing from the argument to the return value
+    pub fn get_argument_taint_changes(&self, arg_offset: usize) -> (Vec<SinkType>, Vec<SinkType>) {
+        (
+            get_for_argument(&self.adds_taints, arg_offset),
+            get_for_argument(&self.sanitizes, arg_offset),
+        )
+    }
+
+    // the (added, removed) taints for data returned from the function's body, which could
+    // have come from any argument
+    pub fn get_all_taint_changes(&self) -> (Vec<SinkType>, Vec<SinkType>) {
+        (
+            self.adds_taints.values().flatten().cloned().collect(),
+            self.sanitizes.values().flatten().cloned().collect(),
+        )
+    }
+
+    // whether data passing through the argument has taints added or removed
+    pub fn changes_argument_taints(&self, arg_offset: usize) -> bool {
+        let arg = arg_offset.to_string();
+
+        [&self.adds_taints, &self.sanitizes]
+            .iter()
+            .any(|taints| taints.contains_key(&arg) || taints.contains_key("*"))
+    }
+}
+
+fn get_for_argument(taints: &FxHashMap<String, Vec<SinkType>>, arg_offset: usize) -> Vec<SinkType> {
+    let mut arg_taints = vec![];
+
+    if let Some(offset_taints) = taints.get(&arg_offset.to_string()) {
+        arg_taints.extend(offset_taints.iter().cloned());
+    }
+
+    if let Some(all_arg_taints) = taints.get("*") {
+        arg_taints.extend(all_arg_taints.iter().cloned());
+    }
+
+    arg_taints
+}
+
+// returns the spec, along with any problems that don't prevent it being used
+pub fn read_from_file(path: &Path) -> Result<(TaintSpec, Vec<String>), Box<dyn Error>> {
+    let file = File::open(path)?;
+    let reader = BufReader::new(file);
+
+    let json_spec: JsonTaintSpec = serde_json::from_reader(reader)?;
+
+    let mut errors = vec![];
+
+    for (function_name, function_spec) in &json_spec.functions {
+        for (field, taints) in [
+            ("sinks", &function_spec.sinks),
+            ("sanitizes", &function_spec.sanitizes),
+            ("adds_taints", &function_spec.adds_taints),
+        ] {
+            for arg in taints.keys() {
+                if arg != "*" && arg.parse::<usize>().is_err() {
+                    errors.push(format!(
+                        "functions[\"{}\"].{}: expected an argument offset or \"*\", got \"{}\"",
+                        function_name, field, arg
+                    ));
+                }
+            }
+        }
+    }
+
+    Ok((
+        TaintSpec {
+            functions: json_spec.functions,
+        },
+        errors,
+    ))
+}
@@ -1,6 +1,10 @@
 use hakana_logger::Logger;
 use hakana_logger::Verbosity;
+use hakana_reflection_info::analysis_result::TruncatedTaintSources;
 use hakana_reflection_info::code_location::FilePath;
+use hakana_reflection_info::code_location::HPos;
+use hakana_reflection_info::codebase_info::CodebaseInfo;
+use hakana_reflection_info::data_flow::node::DataFlowNode;
 use hakana_reflection_info::data_flow::node::DataFlowNodeId;
 use hakana_reflection_info::data_flow::node::DataFlowNodeKind;
 use hakana_reflection_info::function_context::FunctionLikeIdentifier;
@@ -26,13 +30,13 @@ pub fn find_tainted_data(
     config: &Config,
     logger: &Logger,
     interner: &Interner,
-) -> Vec<Issue> {
+) -> (Vec<Issue>, TruncatedTaintSources) {
     let mut new_issues = vec![];
 
     let sources = graph
         .sources
         .values()
-        .map(|v| Rc::new(TaintedNode::from(v)))
+        .map(|v| Rc::new(TaintedNode::from(v, &config.security_config.taint_policy)))
         .collect::<Vec<_>>();
 
     logger.log_sync("Security analysis: detecting paths");
@@ -56,7 +60,7 @@ pub fn find_tainted_data(
     //     }
     // }
 
-    find_paths_to_sinks(
+    let truncated_taint_sources = find_paths_to_sinks(
         sources,
         graph,
         config,
@@ -66,7 +70,7 @@ pub fn find_tainted_data(
         interner,
     );
 
-    new_issues
+    (new_issues, truncated_taint_sources)
 }
 
 pub fn find_connections(
@@ -74,14 +78,14 @@ pub fn find_connections(
     config: &Config,
     logger: &Logger,
     interner: &Interner,
-) -> Vec<Issue> {
+) -> (Vec<Issue>, TruncatedTaintSources) {
     let mut new_issues = vec![];
 
     let sources = graph
         .sources
         .iter()
         .filter(|(_, v)| matches!(v.kind, DataFlowNodeKind::DataSource { .. }))
-        .map(|(_, v)| Rc::new(TaintedNode::from(v)))
+        .map(|(_, v)| Rc::new(TaintedNode::from(v, &config.security_config.taint_policy)))
         .collect::<Vec<_>>();
 
     logger.log_sync(&format!(" - initial sources count: {}", sources.len()));
@@ -96,7 +100,7 @@ pub fn find_connections(
     //     }
     // }
 
-    find_paths_to_sinks(
+    let truncated_taint_sources = find_paths_to_sinks(
         sources,
         graph,
         config,
@@ -106,7 +110,170 @@ pub fn find_connections(
         interner,
     );
 
-    new_issues
+    (new_issues, truncated_taint_sources)
+}
+
+// Connects every --source given to find-paths to a DataSource node targeting every --sink
+pub fn add_query_sources(
+    graph: &mut DataFlowGraph,
+    config: &Config,
+    codebase: &CodebaseInfo,
+    logger: &Logger,
+    interner: &Interner,
+) {
+    let mut sink_ids = vec![];
+
+    for query_sink in &config.security_config.query_sinks {
+        if let Some(node_ids) = query_sink.get_node_ids(codebase, interner) {
+            sink_ids.extend(node_ids.into_iter().map(|(id, _)| id.to_string(interner)));
+        } else {
+            logger.log_sync(&format!(" - could not find sink {}", query_sink.symbol));
+        }
+    }
+
+    for query_source in &config.security_config.query_sources {
+        let node_ids = if let Some(node_ids) = query_source.get_node_ids(codebase, interner) {
+            node_ids
+        } else {
+            logger.log_sync(&format!(" - could not find source {}", query_source.symbol));
+            continue;
+        };
+
+        for (source_id, pos) in node_ids {
+            // nothing can flow out of a symbol that no analyzed code touches
+            if graph.get_node(&source_id).is_none() {
+                logger.log_sync(&format!(
+                    " - source {} is never used",
+                    source_id.to_string(interner)
+                ));
+                continue;
+            }
+
+            let source_node = DataFlowNode {
+                id: source_id.clone(),
+                kind: DataFlowNodeKind::Vertex {
+                    pos: Some(pos),
+                    is_specialized: false,
+                },
+            };
+
+            for sink_id in &sink_ids {
+                // one DataSource per sink, since each only targets a single node
+                let data_source = DataFlowNode {
+                    id: DataFlowNodeId::String(format!(
+                        "{} (to {})",
+                        source_id.to_string(interner),
+                        sink_id
+                    )),
+                    kind: DataFlowNodeKind::DataSource {
+                        pos,
+                        target_id: sink_id.clone(),
+                    },
+                };
+
+                graph.add_path(
+                    &data_source,
+                    &source_node,
+                    PathKind::Default,
+                    vec![],
+                    vec![],
+                );
+                graph.add_node(data_source);
+            }
+        }
+    }
+}
+
+// Connects the arguments of StoresTaint functions to the return values of LoadsTaint
+// functions for the same channel, so that data can be followed through storage
+pub fn add_taint_storage_paths(graph: &mut DataFlowGraph, codebase: &CodebaseInfo) {
+    for (functionlike_id, functionlike_info) in &codebase.functionlike_infos {
+        if functionlike_info.stored_taint_channels.is_empty()
+            && functionlike_info.loaded_taint_channels.is_empty()
+        {
+            continue;
+        }
+
+        let functionlike_id = if functionlike_id.1 == StrId::EMPTY {
+            FunctionLikeIdentifier::Function(functionlike_id.0)
+        } else {
+            FunctionLikeIdentifier::Method(functionlike_id.0, functionlike_id.1)
+        };
+
+        for channel in &functionlike_info.stored_taint_channels {
+            let storage_node = get_taint_storage_node(channel);
+
+            for (offset, param) in functionlike_info.params.iter().enumerate() {
+                let argument_node = DataFlowNode::get_for_method_argument(
+                    &functionlike_id,
+                    offset,
+                    Some(param.location),
+                    None,
+                );
+
+                graph.add_path(
+                    &argument_node,
+                    &storage_node,
+                    PathKind::Default,
+                    vec![],
+                    vec![],
+                );
+            }
+
+            graph.add_node(storage_node);
+        }
+
+        for channel in &functionlike_info.loaded_taint_channels {
+            let storage_node = get_taint_storage_node(channel);
+
+            let return_node = DataFlowNode::get_for_method_return(
+                &functionlike_id,
+                Some(functionlike_info.def_location),
+                None,
+            );
+
+            // loaders that are never called (or have no body) have no return node yet
+            if graph.get_node(&return_node.id).is_none() {
+                graph.add_node(return_node.clone());
+            }
+
+            // specialized calls only have edges from their own return nodes
+            let mut return_node_ids = vec![return_node.id.clone()];
+
+            if let Some(specializations) = graph.specializations.get(&return_node.id) {
+                return_node_ids.extend(
+                    specializations
+                        .iter()
+                        .map(|(file_path, offset)| return_node.id.specialize(*file_path, *offset)),
+                );
+            }
+
+            for return_node_id in return_node_ids {
+                graph.add_path(
+                    &storage_node,
+                    &DataFlowNode {
+                        id: return_node_id,
+                        kind: return_node.kind.clone(),
+                    },
+                    PathKind::Default,
+                    vec![],
+                    vec![],
+                );
+            }
+
+            graph.add_node(storage_node);
+        }
+    }
+}
+
+fn get_taint_storage_node(channel: &str) -> DataFlowNode {
+    DataFlowNode {
+        id: DataFlowNodeId::TaintStorage(channel.to_string()),
+        kind: DataFlowNodeKind::Vertex {
+            pos: None,
+            is_specialized: false,
+        },
+    }
 }
 
 #[inline]
@@ -118,15 +285,21 @@ fn find_paths_to_sinks(
     new_issues: &mut Vec<Issue>,
     match_sinks: bool,
     interner: &Interner,
-) {
+) -> TruncatedTaintSources {
     let mut seen_sources = FxHashSet::default();
 
     for source in &sources {
         seen_sources.insert(source.get_unique_source_id(interner));
     }
 
+    let start_time = Instant::now();
+
+    // keyed by the id of the source a path started from
+    let mut truncated_sources = FxHashMap::default();
+    let mut source_node_counts = FxHashMap::default();
+
     if !match_sinks || !graph.sinks.is_empty() {
-        for i in 0..config.security_config.max_depth {
+        for _ in 0..config.security_config.max_depth {
             if !sources.is_empty() {
                 let now = if matches!(
                     logger.get_verbosity(),
@@ -141,7 +314,28 @@ fn find_paths_to_sinks(
 
                 let mut file_nodes = FxHashMap::default();
 
-                for source in sources {
+                let mut sources_iter = sources.into_iter();
+                let mut out_of_time = false;
+
+                for source in sources_iter.by_ref() {
+                    if let Some(time_budget) = config.security_config.time_budget {
+                        if start_time.elapsed() > time_budget {
+                            add_truncated_source(
+                                &mut truncated_sources,
+                                &source,
+                                PathTruncation::TimeBudget,
+                            );
+                            out_of_time = true;
+                            break;
+                        }
+                    }
+
+                    let original_source_id = &source.get_source().id;
+
+                    if truncated_sources.contains_key(original_source_id) {
+                        continue;
+                    }
+
                     let inow = if matches!(
                         logger.get_verbosity(),
                         Verbosity::Debugging | Verbosity::Timing
@@ -153,11 +347,11 @@ fn find_paths_to_sinks(
                     let source_taints = source.taint_sinks.clone();
                     let source_id = source.id.clone();
 
-                    let generated_sources = get_specialized_sources(graph, source);
+                    let generated_sources = get_specialized_sources(graph, source.clone());
                     actual_source_count += generated_sources.len();
 
                     for generated_source in generated_sources {
-                        new_sources.extend(get_child_nodes(
+                        let child_nodes = get_child_nodes(
                             graph,
                             config,
                             &generated_source,
@@ -165,10 +359,25 @@ fn find_paths_to_sinks(
                             &mut seen_sources,
                             &mut file_nodes,
                             new_issues,
-                            i == config.security_config.max_depth - 1,
                             match_sinks,
                             interner,
-                        ))
+                        );
+
+                        let source_node_count = source_node_counts
+                            .entry(original_source_id.clone())
+                            .or_insert(0);
+                        *source_node_count += child_nodes.len();
+
+                        if *source_node_count > config.security_config.max_nodes_per_source {
+                            add_truncated_source(
+                                &mut truncated_sources,
+                                &source,
+                                PathTruncation::NodeBudget,
+                            );
+                            break;
+                        }
+
+                        new_sources.extend(child_nodes);
                     }
 
                     if let Some(inow) = inow {
@@ -208,10 +417,88 @@ fn find_paths_to_sinks(
                     }
                 }
 
+                // anything left unexplored ran out of time
+                if out_of_time {
+                    for source in sources_iter.chain(new_sources) {
+                        add_truncated_source(
+                            &mut truncated_sources,
+                            &source,
+                            PathTruncation::TimeBudget,
+                        );
+                    }
+
+                    sources = vec![];
+                    break;
+                }
+
                 sources = new_sources;
             }
         }
+
+        // paths that could have gone on past the last step were cut off by max_depth
+        for source in sources {
+            if !get_specialized_sources(graph, source.clone()).is_empty() {
+                add_truncated_source(&mut truncated_sources, &source, PathTruncation::Depth);
+            }
+        }
     }
+
+    let mut truncated_taint_sources = TruncatedTaintSources::default();
+
+    for (original_source_id, (pos, path_truncation)) in truncated_sources {
+        match path_truncation {
+            PathTruncation::Depth => truncated_taint_sources.by_depth += 1,
+            PathTruncation::NodeBudget => truncated_taint_sources.by_node_budget += 1,
+            PathTruncation::TimeBudget => truncated_taint_sources.by_time_budget += 1,
+        }
+
+        if !config.security_config.report_truncated_paths {
+            continue;
+        }
+
+        if let Some(pos) = pos {
+            new_issues.push(Issue::new(
+                IssueKind::TaintAnalysisTruncated,
+                format!(
+                    "Paths from {} were not fully explored because {}",
+                    original_source_id.to_label(interner),
+                    match path_truncation {
+                        PathTruncation::Depth => "they got too long",
+                        PathTruncation::NodeBudget => "they reached too many nodes",
+                        PathTruncation::TimeBudget => "the analysis ran out of time",
+                    }
+                ),
+                pos,
+                &None,
+            ));
+        }
+    }
+
+    truncated_taint_sources
+}
+
+enum PathTruncation {
+    Depth,
+    NodeBudget,
+    TimeBudget,
+}
+
+// only the first reason a source's paths were cut short is kept
+fn add_truncated_source(
+    truncated_sources: &mut FxHashMap<DataFlowNodeId, (Option<HPos>, PathTruncation)>,
+    tainted_node: &TaintedNode,
+    path_truncation: PathTruncation,
+) {
+    let original_source = tainted_node.get_source();
+
+    truncated_sources
+        .entry(original_source.id.clone())
+        .or_insert_with(|| {
+            (
+                original_source.pos.as_ref().map(|pos| **pos),
+                path_truncation,
+            )
+        });
 }
 
 fn get_specialized_sources(graph: &DataFlowGraph, source: Rc<TaintedNode>) -> Vec<Rc<TaintedNode>> {
@@ -281,7 +568,6 @@ fn get_child_nodes(
     seen_sources: &mut FxHashSet<String>,
     file_nodes: &mut FxHashMap<(FilePath, u32), usize>,
     new_issues: &mut Vec<Issue>,
-    is_last: bool,
     match_sinks: bool,
     interner: &Interner,
 ) -> Vec<Rc<TaintedNode>> {
@@ -297,12 +583,14 @@ fn get_child_nodes(
                             target_id,
                             generated_source.get_trace(interner, &config.root_dir)
                         );
-                        new_issues.push(Issue::new(
+                        let mut issue = Issue::new(
                             IssueKind::TaintedData(Box::new(t.clone())),
                             message,
                             **generated_source.pos.as_ref().unwrap(),
                             &None,
-                        ));
+                        );
+                        issue.taint_trace = Some(generated_source.get_trace_steps(interner));
+                        new_issues.push(issue);
                     }
                 }
             }
@@ -375,12 +663,14 @@ fn get_child_nodes(
                                 target_id,
                                 generated_source.get_trace(interner, &config.root_dir)
                             );
-                            new_issues.push(Issue::new(
+                            let mut issue = Issue::new(
                                 IssueKind::TaintedData(Box::new(t.clone())),
                                 message,
                                 **generated_source.pos.as_ref().unwrap(),
                                 &None,
-                            ));
+                            );
+                            issue.taint_trace = Some(generated_source.get_trace_steps(interner));
+                            new_issues.push(issue);
                         }
                     }
                 }
@@ -390,7 +680,8 @@ fn get_child_nodes(
             new_taints.extend(path.added_taints.clone());
             new_taints.retain(|t| !path.removed_taints.contains(t));
 
-            let mut new_destination = TaintedNode::from(destination_node);
+            let mut new_destination =
+                TaintedNode::from(destination_node, &config.security_config.taint_policy);
 
             new_destination.previous = Some(generated_source.clone());
             new_destination.taint_sinks.clone_from(&new_taints);
@@ -422,6 +713,15 @@ fn get_child_nodes(
                             let taint_sources = generated_source.get_taint_sources();
                             for taint_source in taint_sources {
                                 for matching_sink in &matching_sinks {
+                                    // the node may have other sources that this flow is forbidden for
+                                    if !config
+                                        .security_config
+                                        .taint_policy
+                                        .forbids(taint_source, matching_sink)
+                                    {
+                                        continue;
+                                    }
+
                                     if !config.allow_data_from_source_in_file(
                                         taint_source,
                                         matching_sink,
@@ -435,16 +735,24 @@ fn get_child_nodes(
 
                                     let message = format!(
                                         "Data from {} found its way to {} using path {}",
-                                        taint_source.get_error_message(),
+                                        config
+                                            .security_config
+                                            .get_source_error_message(taint_source),
                                         matching_sink.get_error_message(),
                                         new_destination.get_trace(interner, &config.root_dir)
                                     );
-                                    new_issues.push(Issue::new(
+                                    let mut issue = Issue::new(
                                         IssueKind::TaintedData(Box::new(matching_sink.clone())),
                                         message,
                                         *sink_pos,
                                         &None,
-                                    ));
+                                    );
+                                    issue.taint_trace =
+                                        Some(new_destination.get_trace_steps(interner));
+                                    issue.taint_source_id = Some(
+                                        new_destination.get_source().get_unique_source_id(interner),
+                                    );
+                                    new_issues.push(issue);
                                 }
                             }
                         }
@@ -467,9 +775,7 @@ fn get_child_nodes(
 
             seen_sources.insert(source_id);
 
-            if !is_last {
-                new_child_nodes.push(Rc::new(new_destination));
-            }
+            new_child_nodes.push(Rc::new(new_destination));
         }
     }
 
@@ -15,7 +15,6 @@ use hakana_reflection_info::issue::{Issue, IssueKind};
 use hakana_reflection_info::t_atomic::TAtomic;
 use hakana_reflection_info::t_union::TUnion;
 use hakana_reflection_info::taint::{string_to_sink_types, SinkType};
-use hakana_str::Interner;
 use hakana_type::type_comparator::type_comparison_result::TypeComparisonResult;
 use hakana_type::type_comparator::union_type_comparator;
 use hakana_type::{add_union_type, get_arraykey, get_int, get_mixed, get_mixed_any, get_nothing};
@@ -511,11 +510,15 @@ fn add_dataflow(
     }
 
     let taints = if matches!(data_flow_graph.kind, GraphKind::WholeProgram(_)) {
-        let mut taints = get_argument_taints(
-            functionlike_id,
-            argument_offset,
-            statements_analyzer.get_interner(),
-        );
+        let mut taints = if let Some(function_taint_spec) = statements_analyzer
+            .get_config()
+            .taint_spec
+            .get(functionlike_id, statements_analyzer.get_interner())
+        {
+            function_taint_spec.get_argument_sinks(argument_offset)
+        } else {
+            vec![]
+        };
 
         if let Some(sinks) = &function_param.taint_sinks {
             taints.extend(sinks.clone());
@@ -708,82 +711,3 @@ pub(crate) fn get_removed_taints_in_comments(
 
     removed_taints
 }
-
-fn get_argument_taints(
-    function_id: &FunctionLikeIdentifier,
-    arg_offset: usize,
-    interner: &Interner,
-) -> Vec<SinkType> {
-    match function_id {
-        FunctionLikeIdentifier::Function(id) => match interner.lookup(id) {
-            "echo" | "print" | "var_dump" => {
-                return vec![SinkType::HtmlTag, SinkType::Output];
-            }
-            "exec" | "passthru" | "pcntl_exec" | "shell_exec" | "system" | "popen"
-            | "proc_open" => {
-                if arg_offset == 0 {
-                    return vec![SinkType::Shell];
-                }
-            }
-            "file_get_contents" | "file_put_contents" | "fopen" | "unlink" | "file" | "mkdir"
-            | "parse_ini_file" | "chown" | "lchown" | "readfile" | "rmdir" | "symlink"
-            | "tempnam" => {
-                if arg_offset == 0 {
-                    return vec![SinkType::FileSystem];
-                }
-            }
-            "copy" | "link" | "move_uploaded_file" | "rename" => {
-                if arg_offset == 0 || arg_offset == 1 {
-                    return vec![SinkType::FileSystem];
-                }
-            }
-            "header" => {
-                if arg_offset == 0 {
-                    // return vec![TaintType::ResponseHeader];
-                }
-            }
-            "igbinary_unserialize"
-            | "unserialize"
-            | "unserialize_pure"
-            | "fb_unserialize"
-            | "fb_compact_unserialize" => {
-                if arg_offset == 0 {
-                    return vec![SinkType::Unserialize];
-                }
-            }
-            // "ldap" => {
-            //     if arg_offset == 1 || arg_offset == 2 {
-            //         return vec![TaintType::Ldap];
-            //     }
-            // }
-            "setcookie" => {
-                if arg_offset == 0 || arg_offset == 1 {
-                    return vec![SinkType::Cookie];
-                }
-            }
-            "curl_init" | "getimagesize" => {
-                if arg_offset == 0 {
-                    return vec![SinkType::CurlUri];
-                }
-            }
-            "curl_setopt" => {
-                if arg_offset == 2 {
-                    return vec![SinkType::CurlHeader];
-                }
-            }
-            _ => {}
-        },
-        FunctionLikeIdentifier::Method(fq_class, method_name) => {
-            if let ("AsyncMysqlConnection", "query") =
-                (interner.lookup(fq_class), interner.lookup(method_name))
-            {
-                if arg_offset == 0 {
-                    return vec![SinkType::Sql];
-                }
-            }
-        }
-        _ => {}
-    }
-
-    vec![]
-}
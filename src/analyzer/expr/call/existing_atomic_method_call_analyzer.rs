@@ -6,6 +6,7 @@ use hakana_reflection_info::method_identifier::MethodIdentifier;
 use hakana_reflection_info::{
     assertion::Assertion,
     data_flow::{node::DataFlowNode, path::PathKind},
+    functionlike_identifier::FunctionLikeIdentifier,
     t_atomic::{DictKey, TAtomic},
     t_union::TUnion,
 };
@@ -29,7 +30,7 @@ use crate::expr::fetch::array_fetch_analyzer::{
 use crate::stmt_analyzer::AnalysisError;
 use crate::{
     expr::{
-        call_analyzer::check_method_args, expression_identifier,
+        call_analyzer::check_method_args, deprecation_analyzer, expression_identifier,
         fetch::array_fetch_analyzer::handle_array_access_on_dict,
     },
     function_analysis_data::FunctionAnalysisData,
@@ -85,6 +86,13 @@ pub(crate) fn analyze(
             false,
         );
 
+    if let Some(method_name_pos) = method_name_pos {
+        analysis_data.add_symbol_use(
+            method_name_pos,
+            (declaring_method_id.0, declaring_method_id.1),
+        );
+    }
+
     if method_id != declaring_method_id {
         if codebase.class_or_trait_extends(&method_id.0, &declaring_method_id.0) {
             let mut child_storage = classlike_storage;
@@ -170,6 +178,15 @@ pub(crate) fn analyze(
         ));
     };
 
+    deprecation_analyzer::check_functionlike(
+        statements_analyzer,
+        &FunctionLikeIdentifier::Method(declaring_method_id.0, declaring_method_id.1),
+        functionlike_storage,
+        statements_analyzer.get_hpos(method_name_pos.unwrap_or(pos)),
+        analysis_data,
+        context,
+    );
+
     let functionlike_template_types = functionlike_storage.template_types.clone();
 
     let mut template_result = TemplateResult::new(
@@ -11,6 +11,7 @@ use std::rc::Rc;
 
 use crate::expr::call::arguments_analyzer;
 use crate::expr::call_analyzer::{apply_effects, check_template_result};
+use crate::expr::deprecation_analyzer;
 use crate::expr::{echo_analyzer, exit_analyzer, expression_identifier, isset_analyzer};
 use crate::function_analysis_data::FunctionAnalysisData;
 use crate::reconciler;
@@ -145,6 +146,19 @@ pub(crate) fn analyze(
         );
     }
 
+    analysis_data.add_symbol_use(expr.0 .0, (name, StrId::EMPTY));
+
+    let functionlike_id = FunctionLikeIdentifier::Function(name);
+
+    deprecation_analyzer::check_functionlike(
+        statements_analyzer,
+        &functionlike_id,
+        function_storage,
+        statements_analyzer.get_hpos(expr.0 .0),
+        analysis_data,
+        context,
+    );
+
     let mut template_result = TemplateResult::new(IndexMap::new(), IndexMap::new());
 
     if !function_storage.template_types.is_empty() {
@@ -153,8 +167,6 @@ pub(crate) fn analyze(
             .extend(function_storage.template_types.clone());
     }
 
-    let functionlike_id = FunctionLikeIdentifier::Function(name);
-
     arguments_analyzer::check_arguments_match(
         statements_analyzer,
         expr.1,
@@ -25,6 +25,7 @@ use std::collections::BTreeMap;
 use std::path::Path;
 use std::sync::Arc;
 
+use crate::config::taint_spec::FunctionTaintSpec;
 use crate::expr::binop::concat_analyzer::{analyze_concat_nodes, get_concat_nodes};
 use crate::expr::fetch::array_fetch_analyzer::handle_array_access_on_dict;
 use crate::expr::variable_fetch_analyzer;
@@ -826,8 +827,25 @@ fn add_dataflow(
 
     data_flow_graph.add_node(function_call_node.clone());
 
+    let function_taint_spec = if let GraphKind::WholeProgram(_) = &data_flow_graph.kind {
+        statements_analyzer
+            .get_config()
+            .taint_spec
+            .get(functionlike_id, statements_analyzer.get_interner())
+    } else {
+        None
+    };
+
     let (param_offsets, variadic_path) =
-        if !functionlike_storage.user_defined && (!expr.2.is_empty() || expr.3.is_some()) {
+        if let Some(passthrough) = function_taint_spec.and_then(|spec| spec.passthrough.as_ref()) {
+            (
+                passthrough
+                    .iter()
+                    .map(|param_offset| (*param_offset, PathKind::Default))
+                    .collect(),
+                None,
+            )
+        } else if !functionlike_storage.user_defined && (!expr.2.is_empty() || expr.3.is_some()) {
             get_special_argument_nodes(
                 functionlike_id,
                 expr,
@@ -838,12 +856,6 @@ fn add_dataflow(
             (vec![], None)
         };
 
-    let added_removed_taints = if let GraphKind::WholeProgram(_) = &data_flow_graph.kind {
-        get_special_added_removed_taints(functionlike_id, statements_analyzer.get_interner())
-    } else {
-        FxHashMap::default()
-    };
-
     let mut last_arg = usize::MAX;
 
     for (param_offset, path_kind) in param_offsets {
@@ -857,7 +869,7 @@ fn add_dataflow(
                 param_offset,
                 arg_pos,
                 pos,
-                &added_removed_taints,
+                &get_argument_taint_changes(function_taint_spec, param_offset),
                 data_flow_graph,
                 &function_call_node,
                 path_kind,
@@ -879,7 +891,7 @@ fn add_dataflow(
                     param_offset,
                     arg_pos,
                     pos,
-                    &added_removed_taints,
+                    &get_argument_taint_changes(function_taint_spec, param_offset),
                     data_flow_graph,
                     &function_call_node,
                     path_kind.clone(),
@@ -895,7 +907,7 @@ fn add_dataflow(
                 expr.2.len(),
                 statements_analyzer.get_hpos(expanded_arg.pos()),
                 pos,
-                &added_removed_taints,
+                &get_argument_taint_changes(function_taint_spec, expr.2.len()),
                 data_flow_graph,
                 &function_call_node,
                 path_kind.clone(),
@@ -904,12 +916,18 @@ fn add_dataflow(
     }
 
     if let GraphKind::WholeProgram(_) = &data_flow_graph.kind {
-        if !functionlike_storage.taint_source_types.is_empty() {
+        let mut taint_source_types = functionlike_storage.taint_source_types.clone();
+
+        if let Some(function_taint_spec) = function_taint_spec {
+            taint_source_types.extend(function_taint_spec.sources.iter().cloned());
+        }
+
+        if !taint_source_types.is_empty() {
             let function_call_node_source = DataFlowNode {
                 id: function_call_node.id.clone(),
                 kind: DataFlowNodeKind::TaintSource {
                     pos: function_call_node.get_pos(),
-                    types: functionlike_storage.taint_source_types.clone(),
+                    types: taint_source_types,
                 },
             };
             data_flow_graph.add_node(function_call_node_source);
@@ -921,6 +939,18 @@ fn add_dataflow(
     stmt_type
 }
 
+// the (added, removed) taints for data passing from the argument to the return value
+pub(crate) fn get_argument_taint_changes(
+    function_taint_spec: Option<&FunctionTaintSpec>,
+    param_offset: usize,
+) -> (Vec<SinkType>, Vec<SinkType>) {
+    if let Some(function_taint_spec) = function_taint_spec {
+        function_taint_spec.get_argument_taint_changes(param_offset)
+    } else {
+        (vec![], vec![])
+    }
+}
+
 pub(crate) fn add_special_param_dataflow(
     statements_analyzer: &StatementsAnalyzer,
     functionlike_id: &FunctionLikeIdentifier,
@@ -928,7 +958,7 @@ pub(crate) fn add_special_param_dataflow(
     param_offset: usize,
     arg_pos: HPos,
     pos: &Pos,
-    added_removed_taints: &FxHashMap<usize, (Vec<SinkType>, Vec<SinkType>)>,
+    added_removed_taints: &(Vec<SinkType>, Vec<SinkType>),
     data_flow_graph: &mut DataFlowGraph,
     function_call_node: &DataFlowNode,
     path_kind: PathKind,
@@ -944,12 +974,7 @@ pub(crate) fn add_special_param_dataflow(
         },
     );
 
-    let (added_taints, removed_taints) =
-        if let Some(added_removed_taints) = added_removed_taints.get(&param_offset) {
-            added_removed_taints.clone()
-        } else {
-            (vec![], vec![])
-        };
+    let (added_taints, removed_taints) = added_removed_taints.clone();
 
     data_flow_graph.add_path(
         &argument_node,
@@ -1547,24 +1572,3 @@ fn get_special_argument_nodes(
         _ => panic!(),
     }
 }
-
-fn get_special_added_removed_taints(
-    functionlike_id: &FunctionLikeIdentifier,
-    interner: &Interner,
-) -> FxHashMap<usize, (Vec<SinkType>, Vec<SinkType>)> {
-    match functionlike_id {
-        FunctionLikeIdentifier::Function(function_name) => match interner.lookup(function_name) {
-            "html_entity_decode" | "htmlspecialchars_decode" => {
-                FxHashMap::from_iter([(0, (vec![SinkType::HtmlTag], vec![]))])
-            }
-            "htmlentities" | "htmlspecialchars" | "strip_tags" | "urlencode" => {
-                FxHashMap::from_iter([(
-                    0,
-                    (vec![], vec![SinkType::HtmlTag, SinkType::HtmlAttributeUri]),
-                )])
-            }
-            _ => FxHashMap::default(),
-        },
-        _ => panic!(),
-    }
-}
@@ -425,12 +425,54 @@ fn add_dataflow(
             }
         }
 
-        if !functionlike_storage.taint_source_types.is_empty() {
+        let function_taint_spec = statements_analyzer.get_config().taint_spec.get(
+            &FunctionLikeIdentifier::Method(method_id.0, method_id.1),
+            statements_analyzer.get_interner(),
+        );
+
+        let mut taint_source_types = functionlike_storage.taint_source_types.clone();
+
+        if let Some(function_taint_spec) = function_taint_spec {
+            taint_source_types.extend(function_taint_spec.sources.iter().cloned());
+
+            // builtins have no body to carry data to the return value, so arguments whose
+            // taints the spec changes are assumed to pass through
+            let passthrough = if let Some(passthrough) = &function_taint_spec.passthrough {
+                passthrough.clone()
+            } else if !functionlike_storage.user_defined {
+                (0..call_expr.1.len())
+                    .filter(|param_offset| {
+                        function_taint_spec.changes_argument_taints(*param_offset)
+                    })
+                    .collect()
+            } else {
+                vec![]
+            };
+
+            for param_offset in passthrough {
+                if let Some((_, arg_expr)) = call_expr.1.get(param_offset) {
+                    add_special_param_dataflow(
+                        statements_analyzer,
+                        &FunctionLikeIdentifier::Method(method_id.0, method_id.1),
+                        functionlike_storage.specialize_call,
+                        param_offset,
+                        statements_analyzer.get_hpos(arg_expr.pos()),
+                        call_pos,
+                        &function_taint_spec.get_argument_taint_changes(param_offset),
+                        data_flow_graph,
+                        &method_call_node,
+                        PathKind::Default,
+                    );
+                }
+            }
+        }
+
+        if !taint_source_types.is_empty() {
             let method_call_node_source = DataFlowNode {
                 id: method_call_node.id.clone(),
                 kind: DataFlowNodeKind::TaintSource {
                     pos: method_call_node.get_pos(),
-                    types: functionlike_storage.taint_source_types.clone(),
+                    types: taint_source_types,
                 },
             };
             data_flow_graph.add_node(method_call_node_source);
@@ -455,7 +497,7 @@ fn add_dataflow(
             0,
             statements_analyzer.get_hpos(call_expr.1[0].1.pos()),
             call_pos,
-            &FxHashMap::default(),
+            &(vec![], vec![]),
             data_flow_graph,
             &method_call_node,
             PathKind::Aggregate,
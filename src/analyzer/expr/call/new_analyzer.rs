@@ -11,6 +11,7 @@ use hakana_type::template::standin_type_replacer::get_most_specific_type_from_bo
 use rustc_hash::FxHashMap;
 
 use crate::expr::call_analyzer::{check_method_args, get_generic_param_for_offset};
+use crate::expr::deprecation_analyzer;
 use crate::expression_analyzer;
 use crate::function_analysis_data::FunctionAnalysisData;
 use crate::scope_analyzer::ScopeAnalyzer;
@@ -101,6 +102,8 @@ pub(crate) fn analyze(
                             ));
                         };
 
+                        analysis_data.add_symbol_use(&id.0, (name_string, StrId::EMPTY));
+
                         let type_resolution_context =
                             statements_analyzer.get_type_resolution_context();
 
@@ -317,14 +320,14 @@ fn analyze_named_constructor(
         );
     }
 
-    if storage.is_deprecated
-        && if let Some(calling_class) = &context.function_context.calling_class {
-            calling_class != &classlike_name
-        } else {
-            true
-        }
-    {
-        // todo complain about deprecated class
+    if !from_static && !from_classname {
+        deprecation_analyzer::check_classlike(
+            statements_analyzer,
+            &classlike_name,
+            statements_analyzer.get_hpos(pos),
+            analysis_data,
+            context,
+        );
     }
 
     let mut generic_type_params = None;
@@ -363,6 +366,15 @@ fn analyze_named_constructor(
 
         let method_storage = codebase.get_method(&declaring_method_id).unwrap();
 
+        deprecation_analyzer::check_functionlike(
+            statements_analyzer,
+            &FunctionLikeIdentifier::Method(declaring_method_id.0, declaring_method_id.1),
+            method_storage,
+            statements_analyzer.get_hpos(pos),
+            analysis_data,
+            context,
+        );
+
         check_method_args(
             statements_analyzer,
             analysis_data,
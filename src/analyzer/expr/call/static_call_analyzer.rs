@@ -1,3 +1,4 @@
+use crate::expr::deprecation_analyzer;
 use crate::expression_analyzer;
 use crate::function_analysis_data::FunctionAnalysisData;
 use crate::scope_analyzer::ScopeAnalyzer;
@@ -107,6 +108,16 @@ pub(crate) fn analyze(
                         })
                     }
                     _ => {
+                        analysis_data.add_symbol_use(&id.0, (*name, StrId::EMPTY));
+
+                        deprecation_analyzer::check_classlike(
+                            statements_analyzer,
+                            name,
+                            statements_analyzer.get_hpos(&id.0),
+                            analysis_data,
+                            context,
+                        );
+
                         let type_resolution_context =
                             statements_analyzer.get_type_resolution_context();
 
@@ -9,16 +9,19 @@ use hakana_reflection_info::{
     t_atomic::{DictKey, TAtomic},
     t_union::TUnion,
 };
+use hakana_str::StrId;
 use hakana_type::{
     get_arraykey, get_keyset, get_literal_int, get_mixed_any, get_nothing, type_combiner,
     wrap_atomic,
 };
 use oxidized::{
+    aast,
     ast::Expr,
     ast_defs::Pos,
     tast::{KvcKind, VcKind},
 };
 
+use crate::expr::tuple_analyzer;
 use crate::{expression_analyzer, scope_analyzer::ScopeAnalyzer};
 use crate::{function_analysis_data::FunctionAnalysisData, stmt_analyzer::AnalysisError};
 use crate::{scope_context::ScopeContext, statements_analyzer::StatementsAnalyzer};
@@ -66,6 +69,95 @@ impl FromStr for TContainerType {
     }
 }
 
+// Handles collection literals the parser left as a class name and list of fields, returning
+// false if the name isn't a known collection
+pub(crate) fn analyze_collection(
+    statements_analyzer: &StatementsAnalyzer,
+    collection_name: &str,
+    items: &[aast::Afield<(), ()>],
+    pos: &Pos,
+    analysis_data: &mut FunctionAnalysisData,
+    context: &mut ScopeContext,
+) -> Result<bool, AnalysisError> {
+    let collection_name = collection_name.trim_start_matches('\\');
+    let collection_name = collection_name
+        .strip_prefix("HH\\")
+        .unwrap_or(collection_name);
+
+    let vc_kind = match collection_name {
+        "vec" => Some(VcKind::Vec),
+        "keyset" => Some(VcKind::Keyset),
+        "Vector" => Some(VcKind::Vector),
+        "ImmVector" => Some(VcKind::ImmVector),
+        "Set" => Some(VcKind::Set),
+        "ImmSet" => Some(VcKind::ImmSet),
+        _ => None,
+    };
+
+    let kvc_kind = match collection_name {
+        "dict" => Some(KvcKind::Dict),
+        "Map" => Some(KvcKind::Map),
+        "ImmMap" => Some(KvcKind::ImmMap),
+        _ => None,
+    };
+
+    if let Some(kvc_kind) = kvc_kind {
+        let fields = items
+            .iter()
+            .filter_map(|item| match item {
+                aast::Afield::AFkvalue(key, value) => Some(aast::Field(key.clone(), value.clone())),
+                aast::Afield::AFvalue(_) => None,
+            })
+            .collect::<Vec<_>>();
+
+        analyze_keyvals(
+            statements_analyzer,
+            &kvc_kind,
+            &fields,
+            pos,
+            analysis_data,
+            context,
+        )?;
+
+        return Ok(true);
+    }
+
+    let values = items
+        .iter()
+        .map(|item| match item {
+            aast::Afield::AFvalue(value) | aast::Afield::AFkvalue(_, value) => value.clone(),
+        })
+        .collect::<Vec<_>>();
+
+    if let Some(vc_kind) = vc_kind {
+        analyze_vals(
+            statements_analyzer,
+            &vc_kind,
+            &values,
+            pos,
+            analysis_data,
+            context,
+        )?;
+
+        return Ok(true);
+    }
+
+    if collection_name == "Pair" && values.len() == 2 {
+        tuple_analyzer::analyze_pair(
+            statements_analyzer,
+            None,
+            &values,
+            pos,
+            analysis_data,
+            context,
+        )?;
+
+        return Ok(true);
+    }
+
+    Ok(false)
+}
+
 pub(crate) fn analyze_vals(
     statements_analyzer: &StatementsAnalyzer,
     vc_kind: &oxidized::tast::VcKind,
@@ -92,21 +184,17 @@ pub(crate) fn analyze_vals(
                 analysis_data.set_expr_type(pos, get_keyset(get_nothing()));
             }
             VcKind::Vector => {
-                analysis_data.set_expr_type(
-                    pos,
-                    wrap_atomic(TAtomic::TNamedObject {
-                        name: statements_analyzer
-                            .get_interner()
-                            .get("HH\\Vector")
-                            .unwrap(),
-                        type_params: Some(vec![get_mixed_any()]),
-                        is_this: false,
-                        extra_types: None,
-                        remapped_params: false,
-                    }),
-                );
+                analysis_data.set_expr_type(pos, get_collection_object(StrId::VECTOR, vec![]));
+            }
+            VcKind::ImmVector => {
+                analysis_data.set_expr_type(pos, get_collection_object(StrId::IMM_VECTOR, vec![]));
+            }
+            VcKind::Set => {
+                analysis_data.set_expr_type(pos, get_collection_object(StrId::SET, vec![]));
+            }
+            VcKind::ImmSet => {
+                analysis_data.set_expr_type(pos, get_collection_object(StrId::IMM_SET, vec![]));
             }
-            _ => {}
         }
 
         return Ok(());
@@ -189,22 +277,29 @@ pub(crate) fn analyze_vals(
             analysis_data.set_expr_type(pos, keyset);
         }
         VcKind::Vector => {
-            let mut new_vec = wrap_atomic(TAtomic::TNamedObject {
-                name: statements_analyzer
-                    .get_interner()
-                    .get("HH\\Vector")
-                    .unwrap(),
-                type_params: Some(vec![get_mixed_any()]),
-                is_this: false,
-                extra_types: None,
-                remapped_params: false,
-            });
-
-            new_vec.parent_nodes = array_creation_info.parent_nodes;
-
-            analysis_data.set_expr_type(pos, new_vec);
+            analysis_data.set_expr_type(
+                pos,
+                get_collection_object(StrId::VECTOR, array_creation_info.parent_nodes),
+            );
+        }
+        VcKind::ImmVector => {
+            analysis_data.set_expr_type(
+                pos,
+                get_collection_object(StrId::IMM_VECTOR, array_creation_info.parent_nodes),
+            );
+        }
+        VcKind::Set => {
+            analysis_data.set_expr_type(
+                pos,
+                get_collection_object(StrId::SET, array_creation_info.parent_nodes),
+            );
+        }
+        VcKind::ImmSet => {
+            analysis_data.set_expr_type(
+                pos,
+                get_collection_object(StrId::IMM_SET, array_creation_info.parent_nodes),
+            );
         }
-        _ => {}
     }
 
     analysis_data.expr_effects.insert(
@@ -215,6 +310,20 @@ pub(crate) fn analyze_vals(
     Ok(())
 }
 
+fn get_collection_object(name: StrId, parent_nodes: Vec<DataFlowNode>) -> TUnion {
+    let mut collection = wrap_atomic(TAtomic::TNamedObject {
+        name,
+        type_params: Some(vec![get_mixed_any()]),
+        is_this: false,
+        extra_types: None,
+        remapped_params: false,
+    });
+
+    collection.parent_nodes = parent_nodes;
+
+    collection
+}
+
 pub(crate) fn analyze_keyvals(
     statements_analyzer: &StatementsAnalyzer,
     kvc_kind: &oxidized::tast::KvcKind,
@@ -500,8 +609,6 @@ fn add_array_value_dataflow(
         .data_flow_graph
         .add_node(new_parent_node.clone());
 
-    // TODO add taint event dispatches
-
     for parent_node in value_type.parent_nodes.iter() {
         analysis_data.data_flow_graph.add_path(
             parent_node,
@@ -555,8 +662,6 @@ fn add_array_key_dataflow(
         .data_flow_graph
         .add_node(new_parent_node.clone());
 
-    // TODO add taint event dispatches
-
     let key_item_single = if key_item_type.is_single() {
         Some(key_item_type.get_single())
     } else {
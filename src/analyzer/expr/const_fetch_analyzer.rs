@@ -40,6 +40,8 @@ pub(crate) fn analyze(
         ));
     };
 
+    analysis_data.add_symbol_use(boxed.pos(), (*name, StrId::EMPTY));
+
     let mut stmt_type = if let Some(constant_storage) = codebase.constant_infos.get(name) {
         if *name == StrId::FILE_CONST {
             get_literal_string(statements_analyzer.get_file_path_actual().to_string())
@@ -0,0 +1,188 @@
+use hakana_reflection_info::code_location::HPos;
+use hakana_reflection_info::functionlike_identifier::FunctionLikeIdentifier;
+use hakana_reflection_info::functionlike_info::FunctionLikeInfo;
+use hakana_reflection_info::issue::{Issue, IssueKind};
+use hakana_reflection_info::t_atomic::TAtomic;
+use hakana_reflection_info::t_union::{TUnion, TypeNode};
+use hakana_str::StrId;
+use rustc_hash::FxHashSet;
+
+use crate::function_analysis_data::FunctionAnalysisData;
+use crate::scope_analyzer::ScopeAnalyzer;
+use crate::scope_context::ScopeContext;
+use crate::statements_analyzer::StatementsAnalyzer;
+
+pub(crate) fn check_classlike(
+    statements_analyzer: &StatementsAnalyzer,
+    classlike_name: &StrId,
+    pos: HPos,
+    analysis_data: &mut FunctionAnalysisData,
+    context: &ScopeContext,
+) {
+    let codebase = statements_analyzer.get_codebase();
+
+    if let Some(classlike_storage) = codebase.classlike_infos.get(classlike_name) {
+        if let Some(message) = &classlike_storage.deprecated {
+            report(
+                statements_analyzer,
+                IssueKind::DeprecatedClass,
+                format!(
+                    "Class {}",
+                    statements_analyzer.get_interner().lookup(classlike_name)
+                ),
+                message,
+                pos,
+                analysis_data,
+                context,
+            );
+        }
+    }
+}
+
+// reports every deprecated class referenced in a param or return type hint
+pub(crate) fn check_type_hint(
+    statements_analyzer: &StatementsAnalyzer,
+    hint_type: &TUnion,
+    pos: HPos,
+    analysis_data: &mut FunctionAnalysisData,
+    context: &ScopeContext,
+) {
+    let mut seen_classlikes = FxHashSet::default();
+
+    for type_node in hint_type.get_all_child_nodes() {
+        if let TypeNode::Atomic(TAtomic::TNamedObject { name, .. } | TAtomic::TEnum { name, .. }) =
+            type_node
+        {
+            if seen_classlikes.insert(*name) {
+                check_classlike(statements_analyzer, name, pos, analysis_data, context);
+            }
+        }
+    }
+}
+
+pub(crate) fn check_functionlike(
+    statements_analyzer: &StatementsAnalyzer,
+    functionlike_id: &FunctionLikeIdentifier,
+    functionlike_storage: &FunctionLikeInfo,
+    pos: HPos,
+    analysis_data: &mut FunctionAnalysisData,
+    context: &ScopeContext,
+) {
+    if let Some(message) = &functionlike_storage.deprecated {
+        let (issue_kind, description) = match functionlike_id {
+            FunctionLikeIdentifier::Method(..) => (IssueKind::DeprecatedMethod, "Method"),
+            _ => (IssueKind::DeprecatedFunction, "Function"),
+        };
+
+        report(
+            statements_analyzer,
+            issue_kind,
+            format!(
+                "{} {}",
+                description,
+                functionlike_id.to_string(statements_analyzer.get_interner())
+            ),
+            message,
+            pos,
+            analysis_data,
+            context,
+        );
+    }
+}
+
+pub(crate) fn check_property(
+    statements_analyzer: &StatementsAnalyzer,
+    classlike_name: &StrId,
+    property_name: &StrId,
+    pos: HPos,
+    analysis_data: &mut FunctionAnalysisData,
+    context: &ScopeContext,
+) {
+    let codebase = statements_analyzer.get_codebase();
+
+    if let Some(property_storage) = codebase.get_property_storage(classlike_name, property_name) {
+        if let Some(message) = &property_storage.deprecated {
+            let interner = statements_analyzer.get_interner();
+
+            report(
+                statements_analyzer,
+                IssueKind::DeprecatedProperty,
+                format!(
+                    "Property {}::${}",
+                    interner.lookup(classlike_name),
+                    interner.lookup(property_name)
+                ),
+                message,
+                pos,
+                analysis_data,
+                context,
+            );
+        }
+    }
+}
+
+pub(crate) fn check_class_constant(
+    statements_analyzer: &StatementsAnalyzer,
+    classlike_name: &StrId,
+    constant_name: &StrId,
+    pos: HPos,
+    analysis_data: &mut FunctionAnalysisData,
+    context: &ScopeContext,
+) {
+    let codebase = statements_analyzer.get_codebase();
+
+    if let Some(classlike_storage) = codebase.classlike_infos.get(classlike_name) {
+        if let Some(constant_storage) = classlike_storage.constants.get(constant_name) {
+            if let Some(message) = &constant_storage.deprecated {
+                let interner = statements_analyzer.get_interner();
+
+                report(
+                    statements_analyzer,
+                    IssueKind::DeprecatedConstant,
+                    format!(
+                        "Constant {}::{}",
+                        interner.lookup(classlike_name),
+                        interner.lookup(constant_name)
+                    ),
+                    message,
+                    pos,
+                    analysis_data,
+                    context,
+                );
+            }
+        }
+    }
+}
+
+fn report(
+    statements_analyzer: &StatementsAnalyzer,
+    issue_kind: IssueKind,
+    symbol_description: String,
+    message: &str,
+    pos: HPos,
+    analysis_data: &mut FunctionAnalysisData,
+    context: &ScopeContext,
+) {
+    // deprecated code is free to use other deprecated code
+    if context
+        .function_context
+        .is_deprecated(statements_analyzer.get_codebase())
+    {
+        return;
+    }
+
+    analysis_data.maybe_add_issue(
+        Issue::new(
+            issue_kind,
+            if message.is_empty() {
+                format!("{} is deprecated", symbol_description)
+            } else {
+                format!("{} is deprecated: {}", symbol_description, message)
+            },
+            pos,
+            &context.function_context.calling_functionlike_id,
+        ),
+        statements_analyzer.get_config(),
+        statements_analyzer.get_file_path_actual(),
+    );
+}
@@ -43,17 +43,13 @@ pub(crate) fn analyze(
 
         let arg_type = analysis_data.get_rc_expr_type(arg_expr.pos()).cloned();
 
-        // TODO handle exit taint sink
-
         context.inside_general_use = true;
 
         argument_analyzer::verify_type(
             statements_analyzer,
             &arg_type.unwrap_or(Rc::new(get_mixed_any())),
             &get_arraykey(false),
-            &FunctionLikeIdentifier::Function(
-                statements_analyzer.get_interner().get("exit").unwrap(),
-            ),
+            &FunctionLikeIdentifier::Function(StrId::EXIT),
             i,
             arg_expr,
             context,
@@ -1,7 +1,7 @@
 use crate::scope_analyzer::ScopeAnalyzer;
 use crate::stmt_analyzer::AnalysisError;
 use crate::{
-    expr::call::arguments_analyzer::get_template_types_for_call,
+    expr::call::arguments_analyzer::get_template_types_for_call, expr::deprecation_analyzer,
     function_analysis_data::FunctionAnalysisData,
 };
 use crate::{scope_context::ScopeContext, statements_analyzer::StatementsAnalyzer};
@@ -138,6 +138,17 @@ pub(crate) fn analyze(
             false,
         );
 
+    analysis_data.add_symbol_use(expr.1.pos(), (*declaring_property_class, prop_name));
+
+    deprecation_analyzer::check_property(
+        statements_analyzer,
+        declaring_property_class,
+        &prop_name,
+        statements_analyzer.get_hpos(expr.1.pos()),
+        analysis_data,
+        context,
+    );
+
     // TODO: self::propertyFetchCanBeAnalyzed
 
     // TODO: handleNonExistentProperty
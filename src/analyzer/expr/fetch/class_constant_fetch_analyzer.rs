@@ -1,3 +1,4 @@
+use crate::expr::deprecation_analyzer;
 use crate::function_analysis_data::FunctionAnalysisData;
 use crate::stmt_analyzer::AnalysisError;
 use crate::{expression_analyzer, scope_analyzer::ScopeAnalyzer};
@@ -42,7 +43,19 @@ pub(crate) fn analyze(
                     &mut is_static,
                     statements_analyzer.get_file_analyzer().resolved_names,
                 ) {
-                    Some(value) => value,
+                    Some(value) => {
+                        analysis_data.add_symbol_use(&id.0, (value, StrId::EMPTY));
+
+                        deprecation_analyzer::check_classlike(
+                            statements_analyzer,
+                            &value,
+                            statements_analyzer.get_hpos(&id.0),
+                            analysis_data,
+                            context,
+                        );
+
+                        value
+                    }
                     None => return Err(AnalysisError::UserError),
                 }
             } else {
@@ -122,6 +135,10 @@ pub(crate) fn analyze(
         }
     };
 
+    if let Some(const_name_id) = statements_analyzer.get_interner().get(const_name) {
+        analysis_data.add_symbol_use(expr.1 .0, (classlike_name, const_name_id));
+    }
+
     let stmt_type = analyse_known_class_constant(
         codebase,
         analysis_data,
@@ -263,6 +280,15 @@ fn analyse_known_class_constant(
             statements_analyzer.get_config(),
             statements_analyzer.get_file_path_actual(),
         );
+    } else {
+        deprecation_analyzer::check_class_constant(
+            statements_analyzer,
+            classlike_name,
+            &const_name,
+            statements_analyzer.get_hpos(pos),
+            analysis_data,
+            context,
+        );
     }
 
     let mut class_constant_type = codebase.get_class_constant_type(
@@ -2,6 +2,7 @@ use super::{
     atomic_property_fetch_analyzer::add_unspecialized_property_fetch_dataflow,
     instance_property_fetch_analyzer,
 };
+use crate::expr::deprecation_analyzer;
 use crate::function_analysis_data::FunctionAnalysisData;
 use crate::stmt_analyzer::AnalysisError;
 use crate::{expression_analyzer, scope_analyzer::ScopeAnalyzer};
@@ -11,6 +12,7 @@ use hakana_reflection_info::data_flow::node::DataFlowNode;
 use hakana_reflection_info::issue::{Issue, IssueKind};
 use hakana_reflection_info::t_atomic::TAtomic;
 use hakana_reflection_info::EFFECT_READ_PROPS;
+use hakana_str::StrId;
 use hakana_type::type_expander::TypeExpansionOptions;
 use hakana_type::{
     get_named_object,
@@ -38,7 +40,7 @@ pub(crate) fn analyze(
         aast::ClassId_::CIexpr(lhs_expr) => {
             if let aast::Expr_::Id(id) = &lhs_expr.2 {
                 let mut is_static = false;
-                if let Some(id) = get_id_name(
+                if let Some(name) = get_id_name(
                     id,
                     &context.function_context.calling_class,
                     context.function_context.calling_class_final,
@@ -46,7 +48,8 @@ pub(crate) fn analyze(
                     &mut is_static,
                     statements_analyzer.get_file_analyzer().resolved_names,
                 ) {
-                    id
+                    analysis_data.add_symbol_use(&id.0, (name, StrId::EMPTY));
+                    name
                 } else {
                     return Err(AnalysisError::InternalError(
                         "Unable to resolve static classlike name".to_string(),
@@ -170,6 +173,14 @@ pub(crate) fn analyze(
             false,
         );
 
+    analysis_data.add_symbol_use(
+        match &stmt_name {
+            aast::ClassGetExpr::CGexpr(stmt_name_expr) => stmt_name_expr.pos(),
+            aast::ClassGetExpr::CGstring(str) => &str.0,
+        },
+        property_id,
+    );
+
     // Handle scoped property fetches
     if context.has_variable(&var_id) {
         let mut stmt_type = (**context.vars_in_scope.get(&var_id).unwrap()).clone();
@@ -214,7 +225,15 @@ pub(crate) fn analyze(
         return Err(AnalysisError::UserError);
     };
 
-    // TODO AtomicPropertyFetchAnalyzer::checkPropertyDeprecation
+    deprecation_analyzer::check_property(
+        statements_analyzer,
+        declaring_property_class,
+        &property_id.1,
+        statements_analyzer.get_hpos(pos),
+        analysis_data,
+        context,
+    );
+
     // TODO ClassLikeAnalyzer::checkPropertyVisibility
     // TODO if ($codebase->alter_code) {
 
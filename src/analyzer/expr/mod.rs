@@ -9,6 +9,7 @@ pub(crate) mod cast_analyzer;
 pub(crate) mod closure_analyzer;
 pub(crate) mod collection_analyzer;
 pub(crate) mod const_fetch_analyzer;
+pub(crate) mod deprecation_analyzer;
 pub(crate) mod echo_analyzer;
 pub(crate) mod exit_analyzer;
 pub mod expression_identifier;
@@ -21,6 +22,7 @@ pub(crate) mod shape_analyzer;
 pub(crate) mod ternary_analyzer;
 pub(crate) mod tuple_analyzer;
 pub(crate) mod unop_analyzer;
+pub(crate) mod upcast_analyzer;
 pub(crate) mod variable_fetch_analyzer;
 pub(crate) mod xml_analyzer;
 pub(crate) mod yield_analyzer;
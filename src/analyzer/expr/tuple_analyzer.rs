@@ -1,5 +1,6 @@
 use crate::expression_analyzer;
 use crate::function_analysis_data::FunctionAnalysisData;
+use crate::scope_analyzer::ScopeAnalyzer;
 use crate::stmt_analyzer::AnalysisError;
 use crate::{scope_context::ScopeContext, statements_analyzer::StatementsAnalyzer};
 use hakana_reflection_info::data_flow::graph::WholeProgramKind;
@@ -10,8 +11,10 @@ use hakana_reflection_info::{
         path::{ArrayDataKind, PathKind},
     },
     t_atomic::TAtomic,
-    t_union::TUnion,
+    t_union::{populate_union_type, TUnion},
 };
+use hakana_reflector::typehint_resolver::get_type_from_hint;
+use hakana_str::StrId;
 use hakana_type::{get_mixed_any, get_nothing, wrap_atomic};
 use oxidized::{aast, ast_defs::Pos};
 
@@ -24,10 +27,100 @@ pub(crate) fn analyze(
     analysis_data: &mut FunctionAnalysisData,
     context: &mut ScopeContext,
 ) -> Result<(), AnalysisError> {
+    let (known_items, parent_nodes) =
+        analyze_fields(statements_analyzer, tuple_fields, analysis_data, context)?;
+
+    let mut new_dict = wrap_atomic(TAtomic::TVec {
+        known_count: Some(known_items.len()),
+        known_items: if !known_items.is_empty() {
+            Some(known_items)
+        } else {
+            None
+        },
+        type_param: Box::new(get_nothing()),
+        non_empty: true,
+    });
+
+    new_dict.parent_nodes = parent_nodes;
+
+    analysis_data.set_expr_type(pos, new_dict);
+
+    Ok(())
+}
+
+// Pairs are accessed like two-element tuples, so they share the same dataflow
+pub(crate) fn analyze_pair(
+    statements_analyzer: &StatementsAnalyzer,
+    targs: Option<(&aast::Targ<()>, &aast::Targ<()>)>,
+    pair_fields: &[aast::Expr<(), ()>],
+    pos: &Pos,
+    analysis_data: &mut FunctionAnalysisData,
+    context: &mut ScopeContext,
+) -> Result<(), AnalysisError> {
+    let (known_items, parent_nodes) =
+        analyze_fields(statements_analyzer, pair_fields, analysis_data, context)?;
+
+    let mut type_params = known_items
+        .into_values()
+        .map(|(_, item_type)| item_type)
+        .collect::<Vec<_>>();
+
+    if let Some((first_targ, second_targ)) = targs {
+        for (i, targ) in [first_targ, second_targ].into_iter().enumerate() {
+            if let Some(mut targ_type) = get_type_from_hint(
+                &targ.1 .1,
+                context.function_context.calling_class.as_ref(),
+                statements_analyzer.get_type_resolution_context(),
+                statements_analyzer.get_file_analyzer().resolved_names,
+                *statements_analyzer.get_file_path(),
+                targ.1 .0.start_offset() as u32,
+            ) {
+                if targ_type.is_placeholder() {
+                    continue;
+                }
+
+                populate_union_type(
+                    &mut targ_type,
+                    &statements_analyzer.get_codebase().symbols,
+                    &context
+                        .function_context
+                        .get_reference_source(&statements_analyzer.get_file_path().0),
+                    &mut analysis_data.symbol_references,
+                    false,
+                );
+
+                if let Some(type_param) = type_params.get_mut(i) {
+                    *type_param = targ_type;
+                }
+            }
+        }
+    }
+
+    let mut new_pair = wrap_atomic(TAtomic::TNamedObject {
+        name: StrId::PAIR,
+        type_params: Some(type_params),
+        is_this: false,
+        extra_types: None,
+        remapped_params: false,
+    });
+
+    new_pair.parent_nodes = parent_nodes;
+
+    analysis_data.set_expr_type(pos, new_pair);
+
+    Ok(())
+}
+
+fn analyze_fields(
+    statements_analyzer: &StatementsAnalyzer,
+    fields: &[aast::Expr<(), ()>],
+    analysis_data: &mut FunctionAnalysisData,
+    context: &mut ScopeContext,
+) -> Result<(BTreeMap<usize, (bool, TUnion)>, Vec<DataFlowNode>), AnalysisError> {
     let mut parent_nodes = vec![];
 
     let mut known_items = BTreeMap::new();
-    for (i, value_expr) in tuple_fields.iter().enumerate() {
+    for (i, value_expr) in fields.iter().enumerate() {
         // Now check types of the values
         expression_analyzer::analyze(
             statements_analyzer,
@@ -55,22 +148,7 @@ pub(crate) fn analyze(
         known_items.insert(i, (false, value_item_type));
     }
 
-    let mut new_dict = wrap_atomic(TAtomic::TVec {
-        known_count: Some(known_items.len()),
-        known_items: if !known_items.is_empty() {
-            Some(known_items)
-        } else {
-            None
-        },
-        type_param: Box::new(get_nothing()),
-        non_empty: true,
-    });
-
-    new_dict.parent_nodes = parent_nodes;
-
-    analysis_data.set_expr_type(pos, new_dict);
-
-    Ok(())
+    Ok((known_items, parent_nodes))
 }
 
 fn add_tuple_value_dataflow(
@@ -84,7 +162,7 @@ fn add_tuple_value_dataflow(
         || (matches!(
             &analysis_data.data_flow_graph.kind,
             GraphKind::WholeProgram(WholeProgramKind::Taint)
-        ) && value_type.has_taintable_value())
+        ) && !value_type.has_taintable_value())
     {
         return None;
     }
@@ -97,8 +175,6 @@ fn add_tuple_value_dataflow(
         .data_flow_graph
         .add_node(new_parent_node.clone());
 
-    // TODO add taint event dispatches
-
     for parent_node in value_type.parent_nodes.iter() {
         analysis_data.data_flow_graph.add_path(
             parent_node,
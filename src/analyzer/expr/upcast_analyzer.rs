@@ -0,0 +1,117 @@
+use std::rc::Rc;
+
+use crate::expression_analyzer;
+use crate::function_analysis_data::FunctionAnalysisData;
+use crate::scope_analyzer::ScopeAnalyzer;
+use crate::scope_context::ScopeContext;
+use crate::statements_analyzer::StatementsAnalyzer;
+use crate::stmt_analyzer::AnalysisError;
+use hakana_reflection_info::issue::{Issue, IssueKind};
+use hakana_reflection_info::t_union::populate_union_type;
+use hakana_reflector::typehint_resolver::get_type_from_hint;
+use hakana_type::get_mixed_any;
+use hakana_type::type_comparator::{
+    type_comparison_result::TypeComparisonResult, union_type_comparator,
+};
+use hakana_type::type_expander::{self, StaticClassType, TypeExpansionOptions};
+use oxidized::aast;
+
+// $x upcast T checks that $x is already a T, and then treats it as one
+pub(crate) fn analyze(
+    statements_analyzer: &StatementsAnalyzer,
+    expr_pos: &aast::Pos,
+    inner_expr: &aast::Expr<(), ()>,
+    hint: &aast::Hint,
+    analysis_data: &mut FunctionAnalysisData,
+    context: &mut ScopeContext,
+    if_body_context: &mut Option<ScopeContext>,
+) -> Result<(), AnalysisError> {
+    expression_analyzer::analyze(
+        statements_analyzer,
+        inner_expr,
+        analysis_data,
+        context,
+        if_body_context,
+    )?;
+
+    let expr_type = analysis_data
+        .get_rc_expr_type(inner_expr.pos())
+        .cloned()
+        .unwrap_or(Rc::new(get_mixed_any()));
+
+    let codebase = statements_analyzer.get_codebase();
+
+    let mut upcast_type = if let Some(upcast_type) = get_type_from_hint(
+        &hint.1,
+        context.function_context.calling_class.as_ref(),
+        statements_analyzer.get_type_resolution_context(),
+        statements_analyzer.get_file_analyzer().resolved_names,
+        *statements_analyzer.get_file_path(),
+        hint.0.start_offset() as u32,
+    ) {
+        upcast_type
+    } else {
+        analysis_data.set_rc_expr_type(expr_pos, expr_type);
+        return Ok(());
+    };
+
+    populate_union_type(
+        &mut upcast_type,
+        &codebase.symbols,
+        &context
+            .function_context
+            .get_reference_source(&statements_analyzer.get_file_path().0),
+        &mut analysis_data.symbol_references,
+        false,
+    );
+
+    type_expander::expand_union(
+        codebase,
+        &Some(statements_analyzer.get_interner()),
+        &mut upcast_type,
+        &TypeExpansionOptions {
+            self_class: context.function_context.calling_class.as_ref(),
+            static_class_type: if let Some(calling_class) = &context.function_context.calling_class
+            {
+                StaticClassType::Name(calling_class)
+            } else {
+                StaticClassType::None
+            },
+            ..Default::default()
+        },
+        &mut analysis_data.data_flow_graph,
+    );
+
+    if !expr_type.is_mixed()
+        && !union_type_comparator::is_contained_by(
+            codebase,
+            &expr_type,
+            &upcast_type,
+            false,
+            false,
+            false,
+            &mut TypeComparisonResult::new(),
+        )
+    {
+        analysis_data.maybe_add_issue(
+            Issue::new(
+                IssueKind::InvalidUpcast,
+                format!(
+                    "Cannot upcast {} to {}",
+                    expr_type.get_id(Some(statements_analyzer.get_interner())),
+                    upcast_type.get_id(Some(statements_analyzer.get_interner())),
+                ),
+                statements_analyzer.get_hpos(expr_pos),
+                &context.function_context.calling_functionlike_id,
+            ),
+            statements_analyzer.get_config(),
+            statements_analyzer.get_file_path_actual(),
+        );
+    }
+
+    upcast_type.parent_nodes = expr_type.parent_nodes.clone();
+
+    analysis_data.set_expr_type(expr_pos, upcast_type);
+
+    Ok(())
+}
@@ -12,7 +12,7 @@ use crate::expr::{
     as_analyzer, binop_analyzer, call_analyzer, cast_analyzer, closure_analyzer,
     collection_analyzer, const_fetch_analyzer, expression_identifier, include_analyzer,
     pipe_analyzer, prefixed_string_analyzer, shape_analyzer, ternary_analyzer, tuple_analyzer,
-    unop_analyzer, variable_fetch_analyzer, xml_analyzer, yield_analyzer,
+    unop_analyzer, upcast_analyzer, variable_fetch_analyzer, xml_analyzer, yield_analyzer,
 };
 use crate::function_analysis_data::FunctionAnalysisData;
 use crate::reconciler;
@@ -28,6 +28,7 @@ use hakana_reflection_info::data_flow::graph::GraphKind;
 use hakana_reflection_info::data_flow::node::DataFlowNode;
 use hakana_reflection_info::data_flow::path::PathKind;
 use hakana_reflection_info::function_context::FunctionLikeIdentifier;
+use hakana_reflection_info::functionlike_parameter::FnParameter;
 use hakana_reflection_info::issue::{Issue, IssueKind};
 use hakana_reflection_info::method_identifier::MethodIdentifier;
 use hakana_reflection_info::t_atomic::TAtomic;
@@ -289,6 +290,13 @@ pub(crate) fn analyze(
                 Rc::new(get_false()),
             );
         }
+        aast::Expr_::Package(_) => {
+            // whether the given package is loaded, which is only known at runtime
+            analysis_data.expr_types.insert(
+                (expr.1.start_offset() as u32, expr.1.end_offset() as u32),
+                Rc::new(get_bool()),
+            );
+        }
         aast::Expr_::Unop(x) => {
             let (unop, inner_expr) = (&x.0, &x.1);
 
@@ -528,33 +536,85 @@ pub(crate) fn analyze(
             )?;
         }
 
-        aast::Expr_::Collection(_)
-        | aast::Expr_::This
+        aast::Expr_::Collection(boxed) => {
+            if !collection_analyzer::analyze_collection(
+                statements_analyzer,
+                &boxed.0 .1,
+                &boxed.2,
+                expr.pos(),
+                analysis_data,
+                context,
+            )? {
+                report_unrecognized_expression(statements_analyzer, expr, analysis_data, context);
+            }
+        }
+        aast::Expr_::Pair(boxed) => {
+            tuple_analyzer::analyze_pair(
+                statements_analyzer,
+                boxed.0.as_ref().map(|targs| (&targs.0, &targs.1)),
+                &[boxed.1.clone(), boxed.2.clone()],
+                expr.pos(),
+                analysis_data,
+                context,
+            )?;
+        }
+        aast::Expr_::Dollardollar(lid) => {
+            variable_fetch_analyzer::analyze(
+                statements_analyzer,
+                lid,
+                &expr.1,
+                analysis_data,
+                context,
+            )?;
+        }
+        aast::Expr_::ReadonlyExpr(boxed) => {
+            expression_analyzer::analyze(
+                statements_analyzer,
+                boxed,
+                analysis_data,
+                context,
+                if_body_context,
+            )?;
+
+            if let Some(stmt_type) = analysis_data.get_rc_expr_type(boxed.pos()).cloned() {
+                analysis_data.set_rc_expr_type(&expr.1, stmt_type);
+            }
+        }
+        aast::Expr_::Upcast(boxed) => {
+            upcast_analyzer::analyze(
+                statements_analyzer,
+                &expr.1,
+                &boxed.0,
+                &boxed.1,
+                analysis_data,
+                context,
+                if_body_context,
+            )?;
+        }
+        aast::Expr_::Nameof(boxed) => {
+            // nameof C is the same as C::class
+            class_constant_fetch_analyzer::analyze(
+                statements_analyzer,
+                (boxed.as_ref(), (&boxed.1, &"class".to_string())),
+                &expr.1,
+                analysis_data,
+                context,
+                if_body_context,
+            )?;
+        }
+        aast::Expr_::MethodCaller(boxed) => {
+            analyze_method_caller(statements_analyzer, boxed, context, analysis_data, expr)?;
+        }
+        aast::Expr_::This
         | aast::Expr_::Omitted
-        | aast::Expr_::Dollardollar(_)
-        | aast::Expr_::ReadonlyExpr(_)
-        | aast::Expr_::Upcast(_)
         | aast::Expr_::ExpressionTree(_)
         | aast::Expr_::Lplaceholder(_)
-        | aast::Expr_::MethodCaller(_)
-        | aast::Expr_::Pair(_)
         | aast::Expr_::ETSplice(_)
         | aast::Expr_::Hole(_)
-        | aast::Expr_::Nameof(_)
         | aast::Expr_::Invalid(_) => {
-            analysis_data.maybe_add_issue(
-                Issue::new(
-                    IssueKind::UnrecognizedExpression,
-                    "Unrecognized expression".to_string(),
-                    statements_analyzer.get_hpos(&expr.1),
-                    &context.function_context.calling_functionlike_id,
-                ),
-                statements_analyzer.get_config(),
-                statements_analyzer.get_file_path_actual(),
-            );
+            report_unrecognized_expression(statements_analyzer, expr, analysis_data, context);
             //return Err(AnalysisError::UserError);
         }
-        aast::Expr_::Package(_) => todo!(),
     }
 
     let newly_called = analysis_data.after_expr_hook_called.insert((
@@ -867,6 +927,139 @@ fn analyze_function_pointer(
     Ok(())
 }
 
+fn report_unrecognized_expression(
+    statements_analyzer: &StatementsAnalyzer,
+    expr: &aast::Expr<(), ()>,
+    analysis_data: &mut FunctionAnalysisData,
+    context: &ScopeContext,
+) {
+    analysis_data.maybe_add_issue(
+        Issue::new(
+            IssueKind::UnrecognizedExpression,
+            "Unrecognized expression".to_string(),
+            statements_analyzer.get_hpos(&expr.1),
+            &context.function_context.calling_functionlike_id,
+        ),
+        statements_analyzer.get_config(),
+        statements_analyzer.get_file_path_actual(),
+    );
+}
+
+// meth_caller(C::class, 'foo') is a closure that takes a C and calls foo on it
+fn analyze_method_caller(
+    statements_analyzer: &StatementsAnalyzer,
+    boxed: &Box<(aast::ClassName, aast::Pstring)>,
+    context: &mut ScopeContext,
+    analysis_data: &mut FunctionAnalysisData,
+    expr: &aast::Expr<(), ()>,
+) -> Result<(), AnalysisError> {
+    let codebase = statements_analyzer.get_codebase();
+    let interner = statements_analyzer.get_interner();
+
+    let class_name = if let Some(class_name) = statements_analyzer
+        .get_file_analyzer()
+        .resolved_names
+        .get(&(boxed.0 .0.start_offset() as u32))
+        .cloned()
+    {
+        class_name
+    } else {
+        return Err(AnalysisError::InternalError(
+            "Cannot resolve class name for meth_caller".to_string(),
+            statements_analyzer.get_hpos(&boxed.0 .0),
+        ));
+    };
+
+    analysis_data.symbol_references.add_reference_to_symbol(
+        &context.function_context,
+        class_name,
+        false,
+    );
+
+    if !codebase.class_or_interface_exists(&class_name) {
+        analysis_data.maybe_add_issue(
+            Issue::new(
+                IssueKind::NonExistentClasslike,
+                format!("Unknown classlike {}", interner.lookup(&class_name)),
+                statements_analyzer.get_hpos(expr.pos()),
+                &context.function_context.calling_functionlike_id,
+            ),
+            statements_analyzer.get_config(),
+            statements_analyzer.get_file_path_actual(),
+        );
+
+        return Ok(());
+    }
+
+    let method_name = if let Some(method_name) = interner
+        .get(&boxed.1 .1)
+        .filter(|method_name| codebase.method_exists(&class_name, method_name))
+    {
+        method_name
+    } else {
+        analysis_data.maybe_add_issue(
+            Issue::new(
+                IssueKind::NonExistentMethod,
+                format!(
+                    "Method {}::{} does not exist",
+                    interner.lookup(&class_name),
+                    &boxed.1 .1
+                ),
+                statements_analyzer.get_hpos(&boxed.1 .0),
+                &context.function_context.calling_functionlike_id,
+            ),
+            statements_analyzer.get_config(),
+            statements_analyzer.get_file_path_actual(),
+        );
+
+        return Ok(());
+    };
+
+    analysis_data
+        .symbol_references
+        .add_reference_to_class_member(&context.function_context, (class_name, method_name), false);
+
+    if let Some(TAtomic::TClosure {
+        mut params,
+        return_type,
+        effects,
+        closure_id,
+    }) = get_closure_from_id(
+        &FunctionLikeIdentifier::Method(class_name, method_name),
+        codebase,
+        &Some(interner),
+        &mut analysis_data.data_flow_graph,
+    ) {
+        params.insert(
+            0,
+            FnParameter {
+                signature_type: Some(Box::new(wrap_atomic(TAtomic::TNamedObject {
+                    name: class_name,
+                    type_params: None,
+                    is_this: false,
+                    extra_types: None,
+                    remapped_params: false,
+                }))),
+                is_inout: false,
+                is_variadic: false,
+                is_optional: false,
+            },
+        );
+
+        analysis_data.set_expr_type(
+            &expr.1,
+            wrap_atomic(TAtomic::TClosure {
+                params,
+                return_type,
+                effects,
+                closure_id,
+            }),
+        );
+    }
+
+    Ok(())
+}
+
 pub(crate) fn add_decision_dataflow(
     statements_analyzer: &StatementsAnalyzer,
     analysis_data: &mut FunctionAnalysisData,
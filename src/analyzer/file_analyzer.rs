@@ -55,13 +55,16 @@ impl<'a> FileAnalyzer<'a> {
             DataFlowGraph::new(self.analysis_config.graph_kind),
             &self.file_source,
             &Vec::from_iter(self.file_source.comments.iter()),
-            &self.get_config().all_custom_issues,
+            self.get_config(),
             None,
             0,
             None,
         );
 
-        if let Some(issue_filter) = &self.get_config().allowed_issues {
+        if let Some(issue_filter) = self
+            .get_config()
+            .get_allowed_issues(&self.file_source.file_path_actual)
+        {
             analysis_data.issue_filter = Some(issue_filter.clone());
         }
 
@@ -108,14 +111,18 @@ impl<'a> FileAnalyzer<'a> {
             }
         }
 
+        let file_path = statements_analyzer
+            .get_file_analyzer()
+            .get_file_source()
+            .file_path;
+
         update_analysis_result_with_tast(
             analysis_data,
             analysis_result,
-            &statements_analyzer
-                .get_file_analyzer()
-                .get_file_source()
-                .file_path,
+            &file_path,
+            (file_path.0, StrId::EMPTY),
             false,
+            self.get_config().in_language_server,
         );
 
         Ok(())
@@ -106,11 +106,17 @@ pub(crate) fn get_formula(
         return Ok(clauses);
     }
 
-    let mut conditional_ref = String::new();
-    conditional_ref += "*";
-    conditional_ref += conditional.1.start_offset().to_string().as_str();
-    conditional_ref += "-";
-    conditional_ref += conditional.1.end_offset().to_string().as_str();
+    let conditional_ref = if let aast::Expr_::Package(package_id) = &conditional.2 {
+        // every check of the same package has the same outcome
+        format!("*package {}", package_id.1)
+    } else {
+        let mut conditional_ref = String::new();
+        conditional_ref += "*";
+        conditional_ref += conditional.1.start_offset().to_string().as_str();
+        conditional_ref += "-";
+        conditional_ref += conditional.1.end_offset().to_string().as_str();
+        conditional_ref
+    };
 
     Ok(vec![Clause::new(
         {
@@ -10,6 +10,7 @@ use hakana_reflection_info::{
     symbol_references::SymbolReferences,
     t_union::TUnion,
 };
+use hakana_str::StrId;
 use hakana_type::template::TemplateBound;
 use oxidized::{ast_defs::Pos, prim_defs::Comment};
 use rustc_hash::{FxHashMap, FxHashSet};
@@ -44,6 +45,8 @@ pub struct FunctionAnalysisData {
     pub migrate_function: Option<bool>,
     pub after_expr_hook_called: FxHashSet<(u32, u32)>,
     pub after_arg_hook_called: FxHashSet<(u32, u32)>,
+    pub symbol_uses: FxHashMap<(u32, u32), (StrId, StrId)>,
+    collect_symbol_uses: bool,
 }
 
 impl FunctionAnalysisData {
@@ -51,7 +54,7 @@ impl FunctionAnalysisData {
         data_flow_graph: DataFlowGraph,
         file_source: &FileSource,
         comments: &Vec<&(Pos, Comment)>,
-        all_custom_issues: &FxHashSet<String>,
+        config: &Config,
         current_stmt_offset: Option<StmtStart>,
         applicable_fixme_start: u32,
         hakana_fixme_or_ignores: Option<
@@ -79,8 +82,9 @@ impl FunctionAnalysisData {
             symbol_references: SymbolReferences::new(),
             issue_filter: None,
             expr_effects: FxHashMap::default(),
-            hakana_fixme_or_ignores: hakana_fixme_or_ignores
-                .unwrap_or(get_hakana_fixmes_and_ignores(comments, all_custom_issues)),
+            hakana_fixme_or_ignores: hakana_fixme_or_ignores.unwrap_or(
+                get_hakana_fixmes_and_ignores(comments, &config.all_custom_issues),
+            ),
             expr_fixme_positions: FxHashMap::default(),
             matched_ignore_positions: FxHashSet::default(),
             issue_counts: FxHashMap::default(),
@@ -88,6 +92,9 @@ impl FunctionAnalysisData {
             migrate_function: None,
             after_arg_hook_called: FxHashSet::default(),
             after_expr_hook_called: FxHashSet::default(),
+            symbol_uses: FxHashMap::default(),
+            // only the language server looks symbols up by position
+            collect_symbol_uses: config.in_language_server,
         }
     }
 
@@ -129,33 +136,10 @@ impl FunctionAnalysisData {
     }
 
     fn add_issue_fixme(&mut self, issue: &Issue) -> bool {
-        if let Some(insertion_start) = &issue.insertion_start {
+        if let Some((insertion_offset, fixme_comment)) = issue.get_fixme_insertion() {
             self.add_replacement(
-                (insertion_start.offset, insertion_start.offset),
-                Replacement::Substitute(
-                    format!(
-                        "/* HAKANA_FIXME[{}]{} */{}",
-                        issue.kind.to_string(),
-                        if let IssueKind::UnusedParameter
-                        | IssueKind::UnusedAssignment
-                        | IssueKind::UnusedAssignmentInClosure
-                        | IssueKind::UnusedAssignmentStatement
-                        | IssueKind::UnusedStatement
-                        | IssueKind::UnusedFunction
-                        | IssueKind::UnusedPrivateMethod = issue.kind
-                        {
-                            "".to_string()
-                        } else {
-                            " ".to_string() + &issue.description
-                        },
-                        if insertion_start.add_newline {
-                            "\n".to_string() + &"\t".repeat(insertion_start.column as usize)
-                        } else {
-                            " ".to_string()
-                        }
-                    )
-                    .to_string(),
-                ),
+                (insertion_offset, insertion_offset),
+                Replacement::Substitute(fixme_comment),
             );
 
             true
@@ -240,6 +224,7 @@ impl FunctionAnalysisData {
                         | IssueKind::InvalidReturnStatement
                         | IssueKind::InvalidReturnType
                         | IssueKind::InvalidReturnValue
+                        | IssueKind::InvalidUpcast
                         | IssueKind::LessSpecificArgument
                         | IssueKind::LessSpecificNestedArgumentType
                         | IssueKind::LessSpecificNestedReturnStatement
@@ -530,6 +515,14 @@ impl FunctionAnalysisData {
         }
     }
 
+    #[inline]
+    pub fn add_symbol_use(&mut self, pos: &Pos, symbol: (StrId, StrId)) {
+        if self.collect_symbol_uses {
+            self.symbol_uses
+                .insert((pos.start_offset() as u32, pos.end_offset() as u32), symbol);
+        }
+    }
+
     pub(crate) fn get_unused_hakana_fixme_positions(&self) -> Vec<(u32, u32, u32, u32, bool)> {
         let mut unused_fixme_positions = vec![];
 
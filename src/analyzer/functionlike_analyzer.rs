@@ -4,6 +4,7 @@ use crate::dataflow::unused_variable_analyzer::{
     add_unused_expression_replacements, check_variables_used,
 };
 use crate::expr::call_analyzer::reconcile_lower_bounds_with_upper_bounds;
+use crate::expr::deprecation_analyzer;
 use crate::expr::fetch::atomic_property_fetch_analyzer;
 use crate::expression_analyzer;
 use crate::file_analyzer::InternalError;
@@ -16,6 +17,7 @@ use crate::{file_analyzer::FileAnalyzer, function_analysis_data::FunctionAnalysi
 use hakana_reflection_info::analysis_result::{AnalysisResult, Replacement};
 use hakana_reflection_info::classlike_info::ClassLikeInfo;
 use hakana_reflection_info::code_location::{FilePath, HPos, StmtStart};
+use hakana_reflection_info::codebase_info::symbols::SymbolKind;
 use hakana_reflection_info::codebase_info::CodebaseInfo;
 use hakana_reflection_info::data_flow::graph::{DataFlowGraph, GraphKind};
 use hakana_reflection_info::data_flow::node::{
@@ -266,11 +268,30 @@ impl<'a> FunctionLikeAnalyzer<'a> {
         let mut context = ScopeContext::new(function_context);
 
         if !stmt.static_ {
+            // a trait with a require class constraint can only ever be used by that class
+            let this_classlike_storage = if let SymbolKind::Trait = classlike_storage.kind {
+                classlike_storage
+                    .required_classlikes
+                    .iter()
+                    .find(|required_classlike| {
+                        !classlike_storage
+                            .all_parent_classes
+                            .contains(required_classlike)
+                            && !classlike_storage
+                                .all_class_interfaces
+                                .contains(required_classlike)
+                    })
+                    .and_then(|required_class| codebase.classlike_infos.get(required_class))
+                    .unwrap_or(classlike_storage)
+            } else {
+                classlike_storage
+            };
+
             let mut this_type = wrap_atomic(TAtomic::TNamedObject {
-                name: classlike_storage.name,
-                type_params: if !classlike_storage.template_types.is_empty() {
+                name: this_classlike_storage.name,
+                type_params: if !this_classlike_storage.template_types.is_empty() {
                     Some(
-                        classlike_storage
+                        this_classlike_storage
                             .template_types
                             .iter()
                             .map(|(param_name, template_map)| {
@@ -437,7 +458,7 @@ impl<'a> FunctionLikeAnalyzer<'a> {
             DataFlowGraph::new(statements_analyzer.get_config().graph_kind),
             statements_analyzer.get_file_analyzer().get_file_source(),
             &statements_analyzer.comments,
-            &self.get_config().all_custom_issues,
+            self.get_config(),
             if let Some(parent_analysis_data) = &parent_analysis_data {
                 parent_analysis_data.current_stmt_offset
             } else {
@@ -459,7 +480,10 @@ impl<'a> FunctionLikeAnalyzer<'a> {
             }
         }
 
-        if let Some(issue_filter) = &statements_analyzer.get_config().allowed_issues {
+        if let Some(issue_filter) = statements_analyzer
+            .get_config()
+            .get_allowed_issues(statements_analyzer.get_file_path_actual())
+        {
             analysis_data.issue_filter = Some(issue_filter.clone());
         }
 
@@ -629,6 +653,16 @@ impl<'a> FunctionLikeAnalyzer<'a> {
         let mut inferred_return_type = None;
 
         if let Some(expected_return_type) = &functionlike_storage.return_type {
+            if let Some(return_type_location) = functionlike_storage.return_type_location {
+                deprecation_analyzer::check_type_hint(
+                    statements_analyzer,
+                    expected_return_type,
+                    return_type_location,
+                    &mut analysis_data,
+                    &context,
+                );
+            }
+
             let mut expected_return_type = expected_return_type.clone();
             type_expander::expand_union(
                 statements_analyzer.get_codebase(),
@@ -795,6 +829,15 @@ impl<'a> FunctionLikeAnalyzer<'a> {
                 .expr_effects
                 .extend(analysis_data.expr_effects);
 
+            if statements_analyzer.get_config().in_language_server {
+                parent_analysis_data
+                    .expr_types
+                    .extend(analysis_data.expr_types);
+                parent_analysis_data
+                    .symbol_uses
+                    .extend(analysis_data.symbol_uses);
+            }
+
             for (kind, count) in analysis_data.issue_counts {
                 *parent_analysis_data.issue_counts.entry(kind).or_insert(0) += count;
             }
@@ -855,14 +898,22 @@ impl<'a> FunctionLikeAnalyzer<'a> {
                     .insert(context.function_context.calling_functionlike_id.unwrap(), b);
             }
 
+            let file_path = statements_analyzer
+                .get_file_analyzer()
+                .get_file_source()
+                .file_path;
+
             update_analysis_result_with_tast(
                 analysis_data,
                 analysis_result,
-                &statements_analyzer
-                    .get_file_analyzer()
-                    .get_file_source()
-                    .file_path,
+                &file_path,
+                context
+                    .function_context
+                    .calling_functionlike_id
+                    .map(|functionlike_id| functionlike_id.to_ref())
+                    .unwrap_or((file_path.0, StrId::EMPTY)),
                 functionlike_storage.ignore_taint_path,
+                statements_analyzer.get_config().in_language_server,
             );
         }
 
@@ -878,6 +929,16 @@ impl<'a> FunctionLikeAnalyzer<'a> {
         statements_analyzer: &mut StatementsAnalyzer,
     ) -> Result<(), AnalysisError> {
         for (i, param) in functionlike_storage.params.iter().enumerate() {
+            if let Some(param_type) = &param.signature_type {
+                deprecation_analyzer::check_type_hint(
+                    statements_analyzer,
+                    param_type,
+                    param.signature_type_location.unwrap_or(param.name_location),
+                    analysis_data,
+                    context,
+                );
+            }
+
             let mut param_type = if let Some(param_type) = &param.signature_type {
                 for type_node in param_type.get_all_child_nodes() {
                     if let hakana_reflection_info::t_union::TypeNode::Atomic(atomic) = type_node {
@@ -1433,7 +1494,9 @@ pub(crate) fn update_analysis_result_with_tast(
     analysis_data: FunctionAnalysisData,
     analysis_result: &mut AnalysisResult,
     file_path: &FilePath,
+    symbol: (StrId, StrId),
     ignore_taint_path: bool,
+    in_language_server: bool,
 ) {
     if !analysis_data.replacements.is_empty() {
         analysis_result
@@ -1454,6 +1517,25 @@ pub(crate) fn update_analysis_result_with_tast(
         }
     }
 
+    if in_language_server {
+        analysis_result
+            .expr_types
+            .entry(*file_path)
+            .or_default()
+            .extend(
+                analysis_data
+                    .expr_types
+                    .into_iter()
+                    .map(|(offsets, t)| (offsets, (*t).clone())),
+            );
+
+        analysis_result
+            .symbol_uses
+            .entry(*file_path)
+            .or_default()
+            .extend(analysis_data.symbol_uses);
+    }
+
     let mut issues_to_emit = analysis_data.issues_to_emit;
 
     issues_to_emit.sort_by(|a, b| a.pos.start_offset.partial_cmp(&b.pos.start_offset).unwrap());
@@ -1464,17 +1546,22 @@ pub(crate) fn update_analysis_result_with_tast(
         .or_default()
         .extend(issues_to_emit.into_iter().unique().collect::<Vec<_>>());
 
+    // references are needed in whole-program mode too, so that cached graphs are invalidated
+    analysis_result
+        .symbol_references
+        .extend(analysis_data.symbol_references);
+
     if let GraphKind::WholeProgram(_) = &analysis_data.data_flow_graph.kind {
         if !ignore_taint_path {
-            analysis_result
-                .program_dataflow_graph
-                .add_graph(analysis_data.data_flow_graph);
+            if let Some(symbol_graph) = analysis_result.symbol_dataflow_graphs.get_mut(&symbol) {
+                symbol_graph.add_graph(analysis_data.data_flow_graph);
+            } else {
+                analysis_result
+                    .symbol_dataflow_graphs
+                    .insert(symbol, analysis_data.data_flow_graph);
+            }
         }
     } else {
-        analysis_result
-            .symbol_references
-            .extend(analysis_data.symbol_references);
-
         for (source_id, c) in analysis_data.data_flow_graph.mixed_source_counts {
             if let Some(existing_count) = analysis_result.mixed_source_counts.get_mut(&source_id) {
                 existing_count.extend(c);
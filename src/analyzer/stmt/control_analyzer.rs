@@ -80,8 +80,8 @@ pub(crate) fn get_control_actions(
 
                 return control_actions;
             }
-            aast::Stmt_::Throw(_) | aast::Stmt_::Return(_) => {
-                if !return_is_exit && stmt.1.is_return() {
+            aast::Stmt_::Throw(_) | aast::Stmt_::Return(_) | aast::Stmt_::YieldBreak => {
+                if !return_is_exit && !stmt.1.is_throw() {
                     return control_return(control_actions);
                 }
 
@@ -467,12 +467,50 @@ pub(crate) fn get_control_actions(
                 control_actions.extend(block_actions);
             }
             aast::Stmt_::Fallthrough => {}
-            aast::Stmt_::YieldBreak => {}
             aast::Stmt_::Using(_) => {}
             aast::Stmt_::Noop => {}
             aast::Stmt_::Markup(_) => {}
             aast::Stmt_::DeclareLocal(_) => {}
-            aast::Stmt_::Match(_) => {}
+            aast::Stmt_::Match(match_stmt) => {
+                let mut all_leave = match_stmt
+                    .arms
+                    .iter()
+                    .any(|arm| matches!(arm.pat, aast::Pattern::PVar(_)))
+                    || if let Some(analysis_data) = analysis_data {
+                        analysis_data
+                            .fully_matched_switch_offsets
+                            .contains(&stmt.0.start_offset())
+                    } else {
+                        false
+                    };
+
+                for arm in &match_stmt.arms {
+                    let arm_actions = get_control_actions(
+                        codebase,
+                        interner,
+                        resolved_names,
+                        &arm.body.0,
+                        analysis_data,
+                        break_context.clone(),
+                        return_is_exit,
+                    );
+
+                    all_leave = all_leave
+                        && arm_actions
+                            .iter()
+                            .filter(|action| *action == &ControlAction::None)
+                            .count()
+                            == 0;
+
+                    control_actions.extend(arm_actions);
+                }
+
+                if all_leave {
+                    return control_actions;
+                }
+
+                control_actions.retain(|action| *action != ControlAction::None);
+            }
         }
     }
 
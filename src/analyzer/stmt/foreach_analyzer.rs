@@ -459,7 +459,37 @@ fn check_iterator_type(
         } = iterator_atomic_type
         {
             match name {
-                StrId::KEYED_CONTAINER | StrId::KEYED_ITERATOR | StrId::KEYED_TRAVERSABLE => {
+                StrId::VECTOR | StrId::IMM_VECTOR => {
+                    has_valid_iterator = true;
+                    key_type = Some(combine_optional_union_types(
+                        key_type.as_ref(),
+                        Some(&get_int()),
+                        codebase,
+                    ));
+                    value_type = Some(combine_optional_union_types(
+                        value_type.as_ref(),
+                        Some(type_params.first().unwrap()),
+                        codebase,
+                    ));
+                }
+                StrId::SET | StrId::IMM_SET => {
+                    has_valid_iterator = true;
+                    key_type = Some(combine_optional_union_types(
+                        key_type.as_ref(),
+                        Some(type_params.first().unwrap()),
+                        codebase,
+                    ));
+                    value_type = Some(combine_optional_union_types(
+                        value_type.as_ref(),
+                        Some(type_params.first().unwrap()),
+                        codebase,
+                    ));
+                }
+                StrId::KEYED_CONTAINER
+                | StrId::KEYED_ITERATOR
+                | StrId::KEYED_TRAVERSABLE
+                | StrId::MAP
+                | StrId::IMM_MAP => {
                     has_valid_iterator = true;
                     key_type = Some(combine_optional_union_types(
                         key_type.as_ref(),
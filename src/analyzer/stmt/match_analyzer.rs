@@ -0,0 +1,287 @@
+use hakana_reflection_info::data_flow::graph::{DataFlowGraph, GraphKind};
+use hakana_reflection_info::{
+    assertion::Assertion,
+    issue::{Issue, IssueKind},
+    t_union::{populate_union_type, TUnion},
+};
+use hakana_reflector::typehint_resolver::get_type_from_hint;
+use hakana_type::get_mixed_any;
+use hakana_type::type_expander::{self, TypeExpansionOptions};
+use oxidized::{aast, aast::Pos, ast::Binop, ast_defs};
+use rustc_hash::FxHashMap;
+use std::rc::Rc;
+
+use crate::{
+    expr::expression_identifier,
+    expression_analyzer,
+    function_analysis_data::FunctionAnalysisData,
+    reconciler::assertion_reconciler,
+    scope_analyzer::ScopeAnalyzer,
+    scope_context::{loop_scope::LoopScope, ScopeContext},
+    statements_analyzer::StatementsAnalyzer,
+    stmt_analyzer::{self, AnalysisError},
+};
+
+use super::if_conditional_analyzer::add_branch_dataflow;
+
+/**
+A match statement is analyzed as the equivalent if/else chain
+
+match ($x) {
+  _: A => { ... }
+  _: B => { ... }
+  _ => { ... }
+}
+
+becomes
+
+if ($x is A) { ... } else if ($x is B) { ... } else { ... }
+
+If the refinements cover every possible type of $x the final arm becomes the else branch,
+so that it's narrowed to what's left and the chain is known to be exhaustive.
+*/
+pub(crate) fn analyze(
+    statements_analyzer: &StatementsAnalyzer,
+    stmt: &aast::StmtMatch<(), ()>,
+    pos: &Pos,
+    analysis_data: &mut FunctionAnalysisData,
+    context: &mut ScopeContext,
+    loop_scope: &mut Option<LoopScope>,
+) -> Result<(), AnalysisError> {
+    context.inside_conditional = true;
+
+    expression_analyzer::analyze(
+        statements_analyzer,
+        &stmt.expr,
+        analysis_data,
+        context,
+        &mut None,
+    )?;
+
+    context.inside_conditional = false;
+
+    add_branch_dataflow(statements_analyzer, &stmt.expr, analysis_data);
+
+    let subject_type = analysis_data
+        .get_rc_expr_type(&stmt.expr.1)
+        .cloned()
+        .unwrap_or(Rc::new(get_mixed_any()));
+
+    let subject = if expression_identifier::get_var_id(
+        &stmt.expr,
+        context.function_context.calling_class.as_ref(),
+        statements_analyzer.get_file_analyzer().resolved_names,
+        Some((
+            statements_analyzer.get_codebase(),
+            statements_analyzer.get_interner(),
+        )),
+    )
+    .is_some()
+    {
+        stmt.expr.clone()
+    } else {
+        let match_var_id = format!("$-tmp_match-{}", stmt.expr.1.start_offset());
+
+        context
+            .vars_in_scope
+            .insert(match_var_id.clone(), subject_type.clone());
+
+        aast::Expr(
+            (),
+            stmt.expr.1.clone(),
+            aast::Expr_::Lvar(Box::new(oxidized::tast::Lid(
+                stmt.expr.1.clone(),
+                (0, match_var_id),
+            ))),
+        )
+    };
+
+    let mut arms = vec![];
+    let mut has_wildcard = false;
+
+    // arms after a wildcard can never be reached
+    for arm in &stmt.arms {
+        if has_wildcard {
+            if statements_analyzer.get_config().find_unused_expressions {
+                let arm_pos = match &arm.pat {
+                    aast::Pattern::PVar(pat_var) => &pat_var.pos,
+                    aast::Pattern::PRefinement(pat_refinement) => &pat_refinement.pos,
+                };
+
+                analysis_data.maybe_add_issue(
+                    Issue::new(
+                        IssueKind::UnevaluatedCode,
+                        "Unreachable match arm after wildcard".to_string(),
+                        statements_analyzer.get_hpos(arm_pos),
+                        &context.function_context.calling_functionlike_id,
+                    ),
+                    statements_analyzer.get_config(),
+                    statements_analyzer.get_file_path_actual(),
+                );
+            }
+
+            continue;
+        }
+
+        has_wildcard = matches!(arm.pat, aast::Pattern::PVar(_));
+
+        arms.push(arm);
+    }
+
+    let is_exhaustive = has_wildcard
+        || is_subject_covered(
+            statements_analyzer,
+            &subject_type,
+            &arms,
+            analysis_data,
+            context,
+        );
+
+    if is_exhaustive {
+        analysis_data
+            .fully_matched_switch_offsets
+            .insert(pos.start_offset());
+    }
+
+    let mut else_block = aast::Block(vec![]);
+
+    for (i, arm) in arms.iter().enumerate().rev() {
+        let (pattern_pos, pattern_id, pattern_hint) = match &arm.pat {
+            aast::Pattern::PVar(pat_var) => (&pat_var.pos, &pat_var.id, None),
+            aast::Pattern::PRefinement(pat_refinement) => (
+                &pat_refinement.pos,
+                &pat_refinement.id,
+                Some(&pat_refinement.hint),
+            ),
+        };
+
+        let mut arm_stmts = vec![];
+
+        if let Some(pattern_id) = pattern_id {
+            arm_stmts.push(aast::Stmt(
+                pattern_pos.clone(),
+                aast::Stmt_::Expr(Box::new(aast::Expr(
+                    (),
+                    pattern_pos.clone(),
+                    aast::Expr_::Binop(Box::new(Binop {
+                        bop: ast_defs::Bop::Eq(None),
+                        lhs: aast::Expr(
+                            (),
+                            pattern_id.0.clone(),
+                            aast::Expr_::Lvar(Box::new(pattern_id.clone())),
+                        ),
+                        rhs: subject.clone(),
+                    })),
+                ))),
+            ));
+        }
+
+        arm_stmts.extend(arm.body.0.clone());
+
+        else_block = match pattern_hint {
+            Some(hint) if !(is_exhaustive && i == arms.len() - 1) => aast::Block(vec![aast::Stmt(
+                if i == 0 {
+                    pos.clone()
+                } else {
+                    pattern_pos.clone()
+                },
+                aast::Stmt_::If(Box::new((
+                    aast::Expr(
+                        (),
+                        pattern_pos.clone(),
+                        aast::Expr_::Is(Box::new((subject.clone(), hint.clone()))),
+                    ),
+                    aast::Block(arm_stmts),
+                    else_block,
+                ))),
+            )]),
+            _ => aast::Block(arm_stmts),
+        };
+    }
+
+    for arm_stmt in &else_block.0 {
+        stmt_analyzer::analyze(
+            statements_analyzer,
+            arm_stmt,
+            analysis_data,
+            context,
+            loop_scope,
+        )?;
+    }
+
+    Ok(())
+}
+
+// whether every possible type of the match subject is handled by one of the arms
+fn is_subject_covered(
+    statements_analyzer: &StatementsAnalyzer,
+    subject_type: &TUnion,
+    arms: &[&aast::StmtMatchArm<(), ()>],
+    analysis_data: &mut FunctionAnalysisData,
+    context: &ScopeContext,
+) -> bool {
+    let codebase = statements_analyzer.get_codebase();
+
+    let mut remaining_type = subject_type.clone();
+
+    for arm in arms {
+        if let aast::Pattern::PRefinement(pat_refinement) = &arm.pat {
+            let hint = &pat_refinement.hint;
+
+            let mut arm_type = if let Some(arm_type) = get_type_from_hint(
+                &hint.1,
+                context.function_context.calling_class.as_ref(),
+                statements_analyzer.get_type_resolution_context(),
+                statements_analyzer.get_file_analyzer().resolved_names,
+                *statements_analyzer.get_file_path(),
+                hint.0.start_offset() as u32,
+            ) {
+                arm_type
+            } else {
+                return false;
+            };
+
+            populate_union_type(
+                &mut arm_type,
+                &codebase.symbols,
+                &context
+                    .function_context
+                    .get_reference_source(&statements_analyzer.get_file_path().0),
+                &mut analysis_data.symbol_references,
+                false,
+            );
+
+            type_expander::expand_union(
+                codebase,
+                &Some(statements_analyzer.get_interner()),
+                &mut arm_type,
+                &TypeExpansionOptions {
+                    self_class: context.function_context.calling_class.as_ref(),
+                    expand_hakana_types: false,
+                    ..Default::default()
+                },
+                &mut DataFlowGraph::new(GraphKind::FunctionBody),
+            );
+
+            // remove everything this arm matches from what's left
+            for arm_atomic in arm_type.types {
+                remaining_type = assertion_reconciler::reconcile(
+                    &Assertion::IsNotType(arm_atomic),
+                    Some(&remaining_type),
+                    false,
+                    None,
+                    statements_analyzer,
+                    analysis_data,
+                    false,
+                    None,
+                    &None,
+                    false,
+                    false,
+                    &FxHashMap::default(),
+                );
+            }
+        }
+    }
+
+    remaining_type.is_nothing()
+}
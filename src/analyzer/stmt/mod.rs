@@ -11,6 +11,7 @@ pub(crate) mod if_conditional_scope;
 pub(crate) mod ifelse_analyzer;
 pub(crate) mod loop_;
 pub(crate) mod loop_analyzer;
+pub(crate) mod match_analyzer;
 pub(crate) mod return_analyzer;
 pub(crate) mod switch_analyzer;
 pub(crate) mod switch_case_analyzer;
@@ -81,19 +81,7 @@ pub(crate) fn analyze(
 
     let codebase = statements_analyzer.get_codebase();
 
-    if let Some(finally_scope) = context.finally_scope.clone() {
-        let mut finally_scope = (*finally_scope).borrow_mut();
-        for (var_id, var_type) in &context.vars_in_scope {
-            if let Some(finally_type) = finally_scope.vars_in_scope.get_mut(var_id) {
-                *finally_type =
-                    Rc::new(combine_union_types(finally_type, var_type, codebase, false));
-            } else {
-                finally_scope
-                    .vars_in_scope
-                    .insert(var_id.clone(), var_type.clone());
-            }
-        }
-    }
+    update_finally_scope(context, statements_analyzer);
 
     context.has_returned = true;
 
@@ -496,6 +484,43 @@ pub(crate) fn analyze(
     Ok(())
 }
 
+// yield break ends a generator the same way a return without a value does
+pub(crate) fn analyze_yield_break(
+    statements_analyzer: &StatementsAnalyzer,
+    analysis_data: &mut FunctionAnalysisData,
+    context: &mut ScopeContext,
+) {
+    update_finally_scope(context, statements_analyzer);
+
+    context.has_returned = true;
+
+    if let Some(functionlike_storage) = statements_analyzer.get_functionlike_info() {
+        handle_inout_at_return(
+            functionlike_storage,
+            context,
+            analysis_data,
+            statements_analyzer.get_interner(),
+        );
+    }
+}
+
+fn update_finally_scope(context: &mut ScopeContext, statements_analyzer: &StatementsAnalyzer) {
+    if let Some(finally_scope) = context.finally_scope.clone() {
+        let codebase = statements_analyzer.get_codebase();
+        let mut finally_scope = (*finally_scope).borrow_mut();
+        for (var_id, var_type) in &context.vars_in_scope {
+            if let Some(finally_type) = finally_scope.vars_in_scope.get_mut(var_id) {
+                *finally_type =
+                    Rc::new(combine_union_types(finally_type, var_type, codebase, false));
+            } else {
+                finally_scope
+                    .vars_in_scope
+                    .insert(var_id.clone(), var_type.clone());
+            }
+        }
+    }
+}
+
 pub(crate) fn handle_inout_at_return(
     functionlike_storage: &FunctionLikeInfo,
     context: &mut ScopeContext,
@@ -578,13 +603,28 @@ fn handle_dataflow(
         let return_node =
             DataFlowNode::get_for_return_expr(statements_analyzer.get_hpos(return_expr.pos()));
 
+        let mut added_taints = functionlike_storage.added_taints.clone();
+        let mut removed_taints = functionlike_storage.removed_taints.clone();
+
+        if let Some(function_taint_spec) = statements_analyzer
+            .get_config()
+            .taint_spec
+            .get(functionlike_id, statements_analyzer.get_interner())
+        {
+            let (spec_added_taints, spec_removed_taints) =
+                function_taint_spec.get_all_taint_changes();
+
+            added_taints.extend(spec_added_taints);
+            removed_taints.extend(spec_removed_taints);
+        }
+
         for parent_node in &inferred_type.parent_nodes {
             data_flow_graph.add_path(
                 parent_node,
                 &return_node,
                 PathKind::Default,
-                functionlike_storage.added_taints.clone(),
-                functionlike_storage.removed_taints.clone(),
+                added_taints.clone(),
+                removed_taints.clone(),
             );
         }
 
@@ -1,4 +1,8 @@
-use hakana_reflection_info::codebase_info::CodebaseInfo;
+use hakana_reflection_info::codebase_info::{symbols::SymbolKind, CodebaseInfo};
+use hakana_reflection_info::issue::{Issue, IssueKind};
+use hakana_reflection_info::t_atomic::TAtomic;
+use hakana_reflection_info::t_union::TUnion;
+use hakana_reflector::simple_type_inferer;
 use hakana_str::{Interner, StrId};
 use hakana_type::{combine_union_types, get_mixed_any};
 use indexmap::IndexMap;
@@ -53,6 +57,11 @@ pub(crate) fn analyze(
 
     add_branch_dataflow(statements_analyzer, stmt.0, analysis_data);
 
+    let subject_type = analysis_data
+        .get_rc_expr_type(&stmt.0 .1)
+        .cloned()
+        .unwrap_or(Rc::new(get_mixed_any()));
+
     let switch_var_id = if let Some(switch_var_id) = expression_identifier::get_var_id(
         stmt.0,
         context.function_context.calling_class.as_ref(),
@@ -76,6 +85,10 @@ pub(crate) fn analyze(
         switch_var_id
     };
 
+    // literal values are only checked for parameters, whose values are narrowed from a declared
+    // type, and not for locals whose literal types were inferred from their assignments
+    let check_literal_values = is_unassigned_param(statements_analyzer, &switch_var_id, context);
+
     let original_context = context.clone();
 
     let mut last_case_exit_type = ControlAction::Break;
@@ -236,6 +249,25 @@ pub(crate) fn analyze(
         }
     }
 
+    if !has_default {
+        let last_case_falls_through = if let Some((i, _)) = cases.last() {
+            case_action_map[i].contains(&ControlAction::None)
+        } else {
+            false
+        };
+
+        check_exhaustiveness(
+            statements_analyzer,
+            stmt.1,
+            &subject_type,
+            check_literal_values,
+            pos,
+            last_case_falls_through,
+            analysis_data,
+            context,
+        );
+    }
+
     analysis_data
         .fully_matched_switch_offsets
         .insert(pos.start_offset());
@@ -248,6 +280,357 @@ pub(crate) fn analyze(
     Ok(())
 }
 
+// reports switches over an enum, bool or literal union that don't handle every value
+fn check_exhaustiveness(
+    statements_analyzer: &StatementsAnalyzer,
+    cases: &[aast::Case<(), ()>],
+    subject_type: &TUnion,
+    check_literal_values: bool,
+    pos: &Pos,
+    last_case_falls_through: bool,
+    analysis_data: &mut FunctionAnalysisData,
+    context: &ScopeContext,
+) {
+    let mut missing_values = if let Some(values) = get_possible_values(
+        statements_analyzer.get_codebase(),
+        subject_type,
+        check_literal_values,
+    ) {
+        values
+    } else {
+        return;
+    };
+
+    for case in cases {
+        if let Some(case_value) = get_case_value(statements_analyzer, &case.0) {
+            missing_values.retain(|value| !is_same_value(value, &case_value));
+        } else {
+            // we can't tell which values a non-literal case handles
+            return;
+        }
+    }
+
+    if missing_values.is_empty() {
+        return;
+    }
+
+    let interner = statements_analyzer.get_interner();
+
+    let issue = Issue::new(
+        IssueKind::NonExhaustiveSwitch,
+        format!(
+            "Switch statement does not handle {}",
+            missing_values
+                .iter()
+                .map(|value| get_value_text(value, interner, false))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        statements_analyzer.get_hpos(pos),
+        &context.function_context.calling_functionlike_id,
+    );
+
+    let config = statements_analyzer.get_config();
+
+    if config.issues_to_fix.contains(&issue.kind) && !config.add_fixmes {
+        // Only fix code that's not already covered by a FIXME
+        if analysis_data.get_matching_hakana_fixme(&issue).is_none() {
+            add_missing_cases(
+                statements_analyzer,
+                cases,
+                &missing_values,
+                pos,
+                last_case_falls_through,
+                analysis_data,
+            );
+        }
+    } else {
+        analysis_data.maybe_add_issue(issue, config, statements_analyzer.get_file_path_actual());
+    }
+}
+
+// appends a case arm that throws for every unhandled value, just before the closing brace
+fn add_missing_cases(
+    statements_analyzer: &StatementsAnalyzer,
+    cases: &[aast::Case<(), ()>],
+    missing_values: &[TAtomic],
+    pos: &Pos,
+    last_case_falls_through: bool,
+    analysis_data: &mut FunctionAnalysisData,
+) {
+    let file_contents = &statements_analyzer
+        .get_file_analyzer()
+        .get_file_source()
+        .file_contents;
+
+    let closing_brace_offset = pos.end_offset() - 1;
+
+    if file_contents.as_bytes().get(closing_brace_offset) != Some(&b'}') {
+        return;
+    }
+
+    let closing_line_start = get_line_start(file_contents, closing_brace_offset);
+
+    // only handle a closing brace on its own line
+    if !file_contents[closing_line_start..closing_brace_offset]
+        .chars()
+        .all(char::is_whitespace)
+    {
+        return;
+    }
+
+    let switch_indent = get_line_indent(file_contents, pos.start_offset());
+
+    let case_indent = if let Some(first_case) = cases.first() {
+        get_line_indent(file_contents, first_case.0.pos().start_offset()).to_string()
+    } else {
+        format!("{}    ", switch_indent)
+    };
+
+    let indent_unit = match case_indent.strip_prefix(switch_indent) {
+        Some(indent_unit) if !indent_unit.is_empty() => indent_unit,
+        _ => "    ",
+    };
+
+    let body_indent = format!("{}{}", case_indent, indent_unit);
+
+    let mut insertion = String::new();
+
+    // falling off the end of the last case is no longer the same as leaving the switch
+    if last_case_falls_through {
+        insertion += &format!("{}break;\n", body_indent);
+    }
+
+    for missing_value in missing_values {
+        insertion += &format!(
+            "{}case {}:\n",
+            case_indent,
+            get_value_text(missing_value, statements_analyzer.get_interner(), true)
+        );
+    }
+
+    insertion += &format!(
+        "{}throw new \\Exception('Unhandled switch case');\n",
+        body_indent
+    );
+
+    // loop bodies are analyzed more than once, but the cases should only be added once
+    if analysis_data
+        .insertions
+        .get(&(closing_line_start as u32))
+        .map_or(false, |existing| existing.contains(&insertion))
+    {
+        return;
+    }
+
+    analysis_data.insert_at(closing_line_start as u32, insertion);
+}
+
+fn get_line_start(file_contents: &str, offset: usize) -> usize {
+    file_contents[..offset]
+        .rfind('\n')
+        .map(|newline_offset| newline_offset + 1)
+        .unwrap_or(0)
+}
+
+fn get_line_indent(file_contents: &str, offset: usize) -> &str {
+    let line_start = get_line_start(file_contents, offset);
+    let line = &file_contents[line_start..offset];
+
+    &line[..line.len() - line.trim_start().len()]
+}
+
+// every value the switch subject can take, if that's a finite set of enum cases, bools and
+// (when check_literal_values is set) literal ints and strings
+fn get_possible_values(
+    codebase: &CodebaseInfo,
+    subject_type: &TUnion,
+    check_literal_values: bool,
+) -> Option<Vec<TAtomic>> {
+    let mut values = vec![];
+
+    for atomic in &subject_type.types {
+        match atomic {
+            TAtomic::TEnumLiteralCase { .. }
+            | TAtomic::TTrue
+            | TAtomic::TFalse
+            | TAtomic::TNull => {
+                values.push(atomic.clone());
+            }
+            TAtomic::TLiteralInt { .. } | TAtomic::TLiteralString { .. }
+                if check_literal_values =>
+            {
+                values.push(atomic.clone());
+            }
+            TAtomic::TBool => {
+                values.push(TAtomic::TTrue);
+                values.push(TAtomic::TFalse);
+            }
+            TAtomic::TEnum { name, .. } => {
+                values.extend(get_enum_cases(codebase, name)?);
+            }
+            TAtomic::TTypeAlias {
+                name: StrId::MEMBER_OF,
+                type_params: Some(type_params),
+                ..
+            } => {
+                if let TAtomic::TNamedObject { name, .. } = type_params.first()?.get_single() {
+                    values.extend(get_enum_cases(codebase, name)?);
+                } else {
+                    return None;
+                }
+            }
+            _ => {
+                return None;
+            }
+        }
+    }
+
+    Some(values)
+}
+
+fn is_unassigned_param(
+    statements_analyzer: &StatementsAnalyzer,
+    var_id: &str,
+    context: &ScopeContext,
+) -> bool {
+    if context.possibly_assigned_var_ids.contains(var_id) {
+        return false;
+    }
+
+    if let Some(functionlike_info) = statements_analyzer.get_functionlike_info() {
+        let interner = statements_analyzer.get_interner();
+
+        functionlike_info
+            .params
+            .iter()
+            .any(|param| interner.lookup(&param.name.0) == var_id)
+    } else {
+        false
+    }
+}
+
+fn get_enum_cases(codebase: &CodebaseInfo, enum_name: &StrId) -> Option<Vec<TAtomic>> {
+    let enum_storage = codebase.classlike_infos.get(enum_name)?;
+
+    if !matches!(enum_storage.kind, SymbolKind::Enum | SymbolKind::EnumClass) {
+        return None;
+    }
+
+    Some(
+        enum_storage
+            .constants
+            .keys()
+            .map(|member_name| TAtomic::TEnumLiteralCase {
+                enum_name: *enum_name,
+                member_name: *member_name,
+                constraint_type: enum_storage.enum_constraint.clone(),
+            })
+            .collect(),
+    )
+}
+
+fn get_case_value(
+    statements_analyzer: &StatementsAnalyzer,
+    case_expr: &aast::Expr<(), ()>,
+) -> Option<TAtomic> {
+    let resolved_names = statements_analyzer.get_file_analyzer().resolved_names;
+
+    if let aast::Expr_::ClassConst(boxed) = &case_expr.2 {
+        let codebase = statements_analyzer.get_codebase();
+
+        let classlike_name = if let aast::ClassId_::CIexpr(lhs_expr) = &boxed.0 .2 {
+            if let aast::Expr_::Id(id) = &lhs_expr.2 {
+                resolved_names.get(&(id.0.start_offset() as u32))?
+            } else {
+                return None;
+            }
+        } else {
+            return None;
+        };
+
+        let member_name = statements_analyzer.get_interner().get(&boxed.1 .1)?;
+
+        let classlike_storage = codebase.classlike_infos.get(classlike_name)?;
+
+        return match classlike_storage.kind {
+            SymbolKind::Enum | SymbolKind::EnumClass => Some(TAtomic::TEnumLiteralCase {
+                enum_name: *classlike_name,
+                member_name,
+                constraint_type: classlike_storage.enum_constraint.clone(),
+            }),
+            _ => codebase
+                .get_classconst_literal_value(classlike_name, &member_name)
+                .cloned(),
+        };
+    }
+
+    let case_type = simple_type_inferer::infer(case_expr, resolved_names)?;
+
+    if !case_type.is_single() {
+        return None;
+    }
+
+    match case_type.get_single_owned() {
+        case_value @ (TAtomic::TLiteralInt { .. }
+        | TAtomic::TLiteralString { .. }
+        | TAtomic::TTrue
+        | TAtomic::TFalse
+        | TAtomic::TNull) => Some(case_value),
+        _ => None,
+    }
+}
+
+fn is_same_value(a: &TAtomic, b: &TAtomic) -> bool {
+    match (a, b) {
+        (
+            TAtomic::TEnumLiteralCase {
+                enum_name: a_enum_name,
+                member_name: a_member_name,
+                ..
+            },
+            TAtomic::TEnumLiteralCase {
+                enum_name: b_enum_name,
+                member_name: b_member_name,
+                ..
+            },
+        ) => a_enum_name == b_enum_name && a_member_name == b_member_name,
+        _ => a == b,
+    }
+}
+
+// how the value is written in Hack, with fully-qualified enum names when used in code
+fn get_value_text(value: &TAtomic, interner: &Interner, fully_qualified: bool) -> String {
+    match value {
+        TAtomic::TEnumLiteralCase {
+            enum_name,
+            member_name,
+            ..
+        } => {
+            let enum_name = interner.lookup(enum_name);
+
+            format!(
+                "{}{}::{}",
+                if fully_qualified && enum_name.contains('\\') {
+                    "\\"
+                } else {
+                    ""
+                },
+                enum_name,
+                interner.lookup(member_name)
+            )
+        }
+        TAtomic::TLiteralString { value } => {
+            format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+        }
+        TAtomic::TLiteralInt { value } => value.to_string(),
+        TAtomic::TTrue => "true".to_string(),
+        TAtomic::TFalse => "false".to_string(),
+        TAtomic::TNull => "null".to_string(),
+        _ => value.get_id(Some(interner)),
+    }
+}
+
 fn update_case_exit_map(
     codebase: &CodebaseInfo,
     interner: &Interner,
@@ -20,7 +20,8 @@ use crate::scope_context::ScopeContext;
 use crate::statements_analyzer::StatementsAnalyzer;
 use crate::stmt::{
     break_analyzer, continue_analyzer, do_analyzer, for_analyzer, foreach_analyzer,
-    ifelse_analyzer, return_analyzer, switch_analyzer, try_analyzer, while_analyzer,
+    ifelse_analyzer, match_analyzer, return_analyzer, switch_analyzer, try_analyzer,
+    while_analyzer,
 };
 use hakana_reflection_info::issue::{Issue, IssueKind};
 use hakana_reflection_info::t_atomic::TAtomic;
@@ -223,19 +224,18 @@ pub(crate) fn analyze(
             }
         }
         aast::Stmt_::Fallthrough => {} // do nothing
-        aast::Stmt_::YieldBreak | aast::Stmt_::Match(_) => {
-            //println!("{:#?}", stmt);
-            analysis_data.maybe_add_issue(
-                Issue::new(
-                    IssueKind::UnrecognizedStatement,
-                    "Unrecognized statement".to_string(),
-                    statements_analyzer.get_hpos(&stmt.0),
-                    &context.function_context.calling_functionlike_id,
-                ),
-                statements_analyzer.get_config(),
-                statements_analyzer.get_file_path_actual(),
-            );
-            return Err(AnalysisError::UserError);
+        aast::Stmt_::YieldBreak => {
+            return_analyzer::analyze_yield_break(statements_analyzer, analysis_data, context);
+        }
+        aast::Stmt_::Match(boxed) => {
+            match_analyzer::analyze(
+                statements_analyzer,
+                boxed,
+                &stmt.0,
+                analysis_data,
+                context,
+                loop_scope,
+            )?;
         }
         aast::Stmt_::DeclareLocal(_) => {}
         aast::Stmt_::Concurrent(boxed) => {
@@ -0,0 +1,328 @@
+use hakana_reflection_info::analysis_result::AnalysisResult;
+use hakana_reflection_info::code_location::FilePath;
+use hakana_reflection_info::issue::Issue;
+use hakana_str::{Interner, StrId};
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+
+// Issues are identified by their kind, file, enclosing symbol and a hash of the
+// offending code rather than by line number, so that unrelated edits elsewhere
+// in a file don't invalidate the baseline.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub(crate) struct BaselineKey {
+    pub kind: String,
+    pub file: String,
+    pub symbol: String,
+    pub snippet_hash: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BaselineEntry {
+    #[serde(flatten)]
+    key: BaselineKey,
+    count: usize,
+}
+
+pub(crate) struct BaselineResult {
+    pub suppressed_count: usize,
+    pub stale_entries: Vec<(BaselineKey, usize)>,
+}
+
+pub(crate) fn write_baseline(
+    baseline_path: &str,
+    analysis_result: &AnalysisResult,
+    interner: &Interner,
+    root_dir: &str,
+) -> io::Result<usize> {
+    let mut file_contents = FxHashMap::default();
+    let mut counts = BTreeMap::new();
+
+    for issues in [
+        &analysis_result.emitted_issues,
+        &analysis_result.emitted_definition_issues,
+    ] {
+        for issue in issues.values().flatten() {
+            *counts
+                .entry(get_baseline_key(
+                    issue,
+                    interner,
+                    root_dir,
+                    &mut file_contents,
+                ))
+                .or_insert(0) += 1;
+        }
+    }
+
+    let issue_count = counts.values().sum();
+
+    let entries = counts
+        .into_iter()
+        .map(|(key, count)| BaselineEntry { key, count })
+        .collect::<Vec<_>>();
+
+    fs::write(baseline_path, serde_json::to_string_pretty(&entries)?)?;
+
+    Ok(issue_count)
+}
+
+// removes every issue that's recorded in the baseline from the analysis result,
+// returning the baseline entries that no longer match any issue
+pub(crate) fn apply_baseline(
+    baseline_path: &str,
+    analysis_result: &mut AnalysisResult,
+    interner: &Interner,
+    root_dir: &str,
+) -> Result<BaselineResult, String> {
+    let baseline_contents = fs::read_to_string(baseline_path)
+        .map_err(|err| format!("Could not read baseline file {}: {}", baseline_path, err))?;
+
+    let entries: Vec<BaselineEntry> = serde_json::from_str(&baseline_contents)
+        .map_err(|err| format!("Invalid baseline file {}: {}", baseline_path, err))?;
+
+    let mut remaining_counts = BTreeMap::new();
+
+    for entry in entries {
+        *remaining_counts.entry(entry.key).or_insert(0) += entry.count;
+    }
+
+    let mut file_contents = FxHashMap::default();
+    let mut suppressed_count = 0;
+
+    for issues in [
+        &mut analysis_result.emitted_issues,
+        &mut analysis_result.emitted_definition_issues,
+    ] {
+        for file_issues in issues.values_mut() {
+            file_issues.retain(|issue| {
+                let key = get_baseline_key(issue, interner, root_dir, &mut file_contents);
+
+                if let Some(remaining_count) = remaining_counts.get_mut(&key) {
+                    if *remaining_count > 0 {
+                        *remaining_count -= 1;
+                        suppressed_count += 1;
+                        return false;
+                    }
+                }
+
+                true
+            });
+        }
+    }
+
+    Ok(BaselineResult {
+        suppressed_count,
+        stale_entries: remaining_counts
+            .into_iter()
+            .filter(|(_, count)| *count > 0)
+            .collect(),
+    })
+}
+
+fn get_baseline_key(
+    issue: &Issue,
+    interner: &Interner,
+    root_dir: &str,
+    file_contents: &mut FxHashMap<FilePath, String>,
+) -> BaselineKey {
+    let file_path = issue.pos.file_path;
+
+    let contents = file_contents
+        .entry(file_path)
+        .or_insert_with(|| fs::read_to_string(interner.lookup(&file_path.0)).unwrap_or_default());
+
+    // whitespace is collapsed so that reformatting the code doesn't change its hash
+    let normalized_snippet = contents
+        .get(issue.pos.start_offset as usize..issue.pos.end_offset as usize)
+        .unwrap_or_default()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    BaselineKey {
+        kind: issue.kind.to_string(),
+        file: file_path.get_relative_path(interner, root_dir),
+        symbol: get_symbol_name(issue, interner),
+        snippet_hash: format!(
+            "{:016x}",
+            xxhash_rust::xxh3::xxh3_64(normalized_snippet.as_bytes())
+        ),
+    }
+}
+
+fn get_symbol_name(issue: &Issue, interner: &Interner) -> String {
+    let (symbol, member) = issue.symbol;
+
+    // issues outside of any function are attributed to the file itself
+    if symbol == issue.pos.file_path.0 {
+        "".to_string()
+    } else if member == StrId::EMPTY {
+        interner.lookup(&symbol).to_string()
+    } else {
+        format!("{}::{}", interner.lookup(&symbol), interner.lookup(&member))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_baseline, get_baseline_key, write_baseline, BaselineKey};
+    use hakana_reflection_info::analysis_result::AnalysisResult;
+    use hakana_reflection_info::code_location::{FilePath, HPos};
+    use hakana_reflection_info::data_flow::graph::GraphKind;
+    use hakana_reflection_info::function_context::FunctionLikeIdentifier;
+    use hakana_reflection_info::issue::{Issue, IssueKind};
+    use hakana_reflection_info::symbol_references::SymbolReferences;
+    use hakana_str::Interner;
+    use rustc_hash::FxHashMap;
+    use std::fs;
+
+    // an issue in function foo covering the first occurrence of snippet
+    fn get_issue(
+        interner: &mut Interner,
+        file_path: FilePath,
+        contents: &str,
+        snippet: &str,
+    ) -> Issue {
+        let start_offset = contents.find(snippet).unwrap() as u32;
+        let start_line = contents[..start_offset as usize].matches('\n').count() as u32 + 1;
+
+        Issue::new(
+            IssueKind::UnusedAssignment,
+            "Assignment to $x is unused".to_string(),
+            HPos {
+                file_path,
+                start_offset,
+                end_offset: start_offset + snippet.len() as u32,
+                start_line,
+                end_line: start_line + snippet.matches('\n').count() as u32,
+                start_column: 1,
+                end_column: 1,
+            },
+            &Some(FunctionLikeIdentifier::Function(
+                interner.intern("foo".to_string()),
+            )),
+        )
+    }
+
+    fn get_key(interner: &mut Interner, contents: &str, snippet: &str) -> BaselineKey {
+        let file_path = FilePath(interner.intern("/root/input.hack".to_string()));
+        let issue = get_issue(interner, file_path, contents, snippet);
+
+        let mut file_contents = FxHashMap::default();
+        file_contents.insert(file_path, contents.to_string());
+
+        get_baseline_key(&issue, interner, "/root", &mut file_contents)
+    }
+
+    #[test]
+    fn key_ignores_line_shifts_and_whitespace() {
+        let mut interner = Interner::default();
+
+        let key = get_key(
+            &mut interner,
+            "function foo(): void {\n  $x = bar(1, 2);\n}\n",
+            "$x = bar(1, 2)",
+        );
+
+        assert_eq!(key.kind, "UnusedAssignment");
+        assert_eq!(key.file, "input.hack");
+        assert_eq!(key.symbol, "foo");
+
+        let shifted_key = get_key(
+            &mut interner,
+            "// a new comment\n\nfunction foo(): void {\n  $x = bar(1, 2);\n}\n",
+            "$x = bar(1, 2)",
+        );
+
+        assert_eq!(key, shifted_key);
+
+        let reformatted_key = get_key(
+            &mut interner,
+            "function foo(): void {\n  $x  =   bar(1,\n    2);\n}\n",
+            "$x  =   bar(1,\n    2)",
+        );
+
+        assert_eq!(key, reformatted_key);
+
+        let changed_key = get_key(
+            &mut interner,
+            "function foo(): void {\n  $x = bar(1, 3);\n}\n",
+            "$x = bar(1, 3)",
+        );
+
+        assert_ne!(key, changed_key);
+    }
+
+    #[test]
+    fn apply_baseline_decrements_counts_and_reports_stale_entries() {
+        let root_dir = std::env::temp_dir().join("hakana_baseline_test");
+        fs::create_dir_all(&root_dir).unwrap();
+        let root_dir = root_dir.to_str().unwrap().to_string();
+        let path = format!("{}/input.hack", root_dir);
+        let baseline_path = format!("{}/baseline.json", root_dir);
+
+        let contents = "function foo(): void {\n  $x = 1;\n  $x = 1;\n  $y = 2;\n  $z = 3;\n}\n";
+        fs::write(&path, contents).unwrap();
+
+        let mut interner = Interner::default();
+        let file_path = FilePath(interner.intern(path));
+
+        // the baseline records $x = 1 once and $y = 2 once
+        let mut analysis_result =
+            AnalysisResult::new(GraphKind::FunctionBody, SymbolReferences::new());
+        analysis_result.emitted_issues.insert(
+            file_path,
+            vec![
+                get_issue(&mut interner, file_path, contents, "$x = 1"),
+                get_issue(&mut interner, file_path, contents, "$y = 2"),
+            ],
+        );
+
+        assert_eq!(
+            write_baseline(&baseline_path, &analysis_result, &interner, &root_dir).unwrap(),
+            2
+        );
+
+        // $x = 1 now appears twice, $y = 2 has been fixed and $z = 3 is new
+        let x_offset = contents.rfind("$x = 1").unwrap() as u32;
+        let mut second_x_issue = get_issue(&mut interner, file_path, contents, "$x = 1");
+        second_x_issue.pos.start_offset = x_offset;
+        second_x_issue.pos.end_offset = x_offset + 6;
+        second_x_issue.pos.start_line = 3;
+        second_x_issue.pos.end_line = 3;
+
+        let mut analysis_result =
+            AnalysisResult::new(GraphKind::FunctionBody, SymbolReferences::new());
+        analysis_result.emitted_issues.insert(
+            file_path,
+            vec![
+                get_issue(&mut interner, file_path, contents, "$x = 1"),
+                second_x_issue,
+                get_issue(&mut interner, file_path, contents, "$z = 3"),
+            ],
+        );
+
+        let baseline_result =
+            apply_baseline(&baseline_path, &mut analysis_result, &interner, &root_dir).unwrap();
+
+        assert_eq!(baseline_result.suppressed_count, 1);
+
+        let remaining_issues = &analysis_result.emitted_issues[&file_path];
+        assert_eq!(remaining_issues.len(), 2);
+        assert_eq!(remaining_issues[0].pos.start_line, 3);
+        assert_eq!(remaining_issues[1].pos.start_line, 5);
+
+        let mut file_contents = FxHashMap::default();
+        let y_issue = get_issue(&mut interner, file_path, contents, "$y = 2");
+
+        assert_eq!(
+            baseline_result.stale_entries,
+            vec![(
+                get_baseline_key(&y_issue, &interner, &root_dir, &mut file_contents),
+                1
+            )]
+        );
+    }
+}
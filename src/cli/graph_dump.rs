@@ -0,0 +1,495 @@
+use hakana_reflection_info::code_location::HPos;
+use hakana_reflection_info::data_flow::graph::DataFlowGraph;
+use hakana_reflection_info::data_flow::node::{DataFlowNode, DataFlowNodeId, DataFlowNodeKind};
+use hakana_reflection_info::taint::{SinkType, SourceType};
+use hakana_str::Interner;
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+#[derive(Serialize)]
+struct JsonGraph {
+    nodes: Vec<JsonNode>,
+    edges: Vec<JsonEdge>,
+}
+
+#[derive(Serialize)]
+struct JsonNode {
+    id: String,
+    label: String,
+    kind: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    file: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    column: Option<u16>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    taints: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct JsonEdge {
+    from: String,
+    to: String,
+    kind: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    added_taints: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    removed_taints: Vec<String>,
+}
+
+// Writes the graph as JSON if the path ends in .json, and as Graphviz DOT otherwise.
+// When `around` is given only nodes within `depth` edges of a node whose id mentions
+// that symbol, or which is positioned in that file or directory, are written.
+pub fn dump_graph(
+    graph: &DataFlowGraph,
+    interner: &Interner,
+    root_dir: &str,
+    path: &Path,
+    around: Option<&str>,
+    depth: usize,
+) -> std::io::Result<()> {
+    let mut nodes = BTreeMap::new();
+
+    for (node_id, node) in graph
+        .vertices
+        .iter()
+        .chain(graph.sources.iter())
+        .chain(graph.sinks.iter())
+    {
+        nodes.insert(node_id.to_string(interner), (node_id, Some(node)));
+    }
+
+    for (from_id, to_ids) in &graph.forward_edges {
+        for node_id in std::iter::once(from_id).chain(to_ids.keys()) {
+            nodes
+                .entry(node_id.to_string(interner))
+                .or_insert((node_id, None));
+        }
+    }
+
+    let included_ids = around.map(|around| {
+        let seed_ids = nodes
+            .iter()
+            .filter(|(node_id_str, (_, node))| {
+                node_id_str.contains(around)
+                    || node
+                        .and_then(get_node_pos)
+                        .map(|pos| {
+                            let file_path = pos.file_path.get_relative_path(interner, root_dir);
+                            file_path == around
+                                || file_path
+                                    .starts_with(&format!("{}/", around.trim_end_matches('/')))
+                        })
+                        .unwrap_or(false)
+            })
+            .map(|(_, (node_id, _))| (*node_id).clone())
+            .collect::<Vec<_>>();
+
+        get_neighbourhood(graph, seed_ids, depth)
+    });
+
+    let is_included = |node_id: &DataFlowNodeId| {
+        included_ids
+            .as_ref()
+            .map(|included_ids| included_ids.contains(node_id))
+            .unwrap_or(true)
+    };
+
+    let json_nodes = nodes
+        .iter()
+        .filter(|(_, (node_id, _))| is_included(node_id))
+        .map(|(node_id_str, (node_id, node))| {
+            let pos = node.and_then(get_node_pos);
+
+            JsonNode {
+                id: node_id_str.clone(),
+                label: node_id.to_label(interner),
+                kind: match node.map(|node| &node.kind) {
+                    Some(DataFlowNodeKind::TaintSource { .. }) => "source",
+                    Some(DataFlowNodeKind::TaintSink { .. }) => "sink",
+                    _ => "vertex",
+                },
+                file: pos.map(|pos| pos.file_path.get_relative_path(interner, root_dir)),
+                line: pos.map(|pos| pos.start_line),
+                column: pos.map(|pos| pos.start_column),
+                taints: match node.map(|node| &node.kind) {
+                    Some(DataFlowNodeKind::TaintSource { types, .. }) => {
+                        types.iter().map(format_source_type).collect()
+                    }
+                    Some(DataFlowNodeKind::TaintSink { types, .. }) => {
+                        types.iter().map(format_sink_type).collect()
+                    }
+                    _ => vec![],
+                },
+            }
+        })
+        .collect::<Vec<_>>();
+
+    let mut json_edges = vec![];
+
+    for (from_id, to_ids) in &graph.forward_edges {
+        if !is_included(from_id) {
+            continue;
+        }
+
+        for (to_id, path) in to_ids {
+            if !is_included(to_id) {
+                continue;
+            }
+
+            json_edges.push(JsonEdge {
+                from: from_id.to_string(interner),
+                to: to_id.to_string(interner),
+                kind: path.kind.to_string(),
+                added_taints: path.added_taints.iter().map(format_sink_type).collect(),
+                removed_taints: path.removed_taints.iter().map(format_sink_type).collect(),
+            });
+        }
+    }
+
+    json_edges.sort_by(|a, b| (&a.from, &a.to).cmp(&(&b.from, &b.to)));
+
+    let json_graph = JsonGraph {
+        nodes: json_nodes,
+        edges: json_edges,
+    };
+
+    let contents = if path.extension().map(|ext| ext == "json").unwrap_or(false) {
+        serde_json::to_string_pretty(&json_graph).unwrap()
+    } else {
+        to_dot(&json_graph)
+    };
+
+    std::fs::write(path, contents)
+}
+
+// Treats edges as undirected, so that both what flows into and out of the seed nodes is kept
+fn get_neighbourhood(
+    graph: &DataFlowGraph,
+    seed_ids: Vec<DataFlowNodeId>,
+    depth: usize,
+) -> FxHashSet<DataFlowNodeId> {
+    let mut neighbours: FxHashMap<&DataFlowNodeId, Vec<&DataFlowNodeId>> = FxHashMap::default();
+
+    for (from_id, to_ids) in &graph.forward_edges {
+        for to_id in to_ids.keys() {
+            neighbours.entry(from_id).or_default().push(to_id);
+            neighbours.entry(to_id).or_default().push(from_id);
+        }
+    }
+
+    let mut included_ids = seed_ids.iter().cloned().collect::<FxHashSet<_>>();
+    let mut frontier = seed_ids;
+
+    for _ in 0..depth {
+        let mut new_frontier = vec![];
+
+        for node_id in &frontier {
+            if let Some(node_neighbours) = neighbours.get(node_id) {
+                for neighbour_id in node_neighbours {
+                    if included_ids.insert((*neighbour_id).clone()) {
+                        new_frontier.push((*neighbour_id).clone());
+                    }
+                }
+            }
+        }
+
+        if new_frontier.is_empty() {
+            break;
+        }
+
+        frontier = new_frontier;
+    }
+
+    included_ids
+}
+
+fn to_dot(json_graph: &JsonGraph) -> String {
+    let mut dot = "digraph dataflow {\n    node [shape=box];\n".to_string();
+
+    let mut node_indexes = FxHashMap::default();
+
+    for (i, node) in json_graph.nodes.iter().enumerate() {
+        node_indexes.insert(&node.id, i);
+
+        let mut label = node.label.clone();
+
+        if let (Some(file), Some(line)) = (&node.file, node.line) {
+            write!(label, "\n{}:{}", file, line).unwrap();
+        }
+
+        if !node.taints.is_empty() {
+            write!(label, "\n[{}]", node.taints.join(", ")).unwrap();
+        }
+
+        let attributes = match node.kind {
+            "source" => ", color=red",
+            "sink" => ", color=blue",
+            _ => "",
+        };
+
+        writeln!(
+            dot,
+            "    n{} [label=\"{}\"{}];",
+            i,
+            escape(&label),
+            attributes
+        )
+        .unwrap();
+    }
+
+    for edge in &json_graph.edges {
+        let mut label = edge.kind.clone();
+
+        if !edge.added_taints.is_empty() {
+            write!(label, "\n+{}", edge.added_taints.join(", +")).unwrap();
+        }
+
+        if !edge.removed_taints.is_empty() {
+            write!(label, "\n-{}", edge.removed_taints.join(", -")).unwrap();
+        }
+
+        writeln!(
+            dot,
+            "    n{} -> n{} [label=\"{}\"];",
+            node_indexes[&edge.from],
+            node_indexes[&edge.to],
+            escape(&label)
+        )
+        .unwrap();
+    }
+
+    dot += "}\n";
+
+    dot
+}
+
+fn escape(str: &str) -> String {
+    str.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn get_node_pos(node: &DataFlowNode) -> Option<HPos> {
+    match &node.kind {
+        DataFlowNodeKind::Vertex { pos, .. } | DataFlowNodeKind::TaintSource { pos, .. } => *pos,
+        DataFlowNodeKind::TaintSink { pos, .. }
+        | DataFlowNodeKind::VariableUseSource { pos, .. }
+        | DataFlowNodeKind::VariableUseSink { pos }
+        | DataFlowNodeKind::DataSource { pos, .. } => Some(*pos),
+        DataFlowNodeKind::ForLoopInit { .. } => None,
+    }
+}
+
+fn format_source_type(source_type: &SourceType) -> String {
+    match source_type {
+        SourceType::Custom(name) => format!("Custom:{}", name),
+        _ => source_type.to_string(),
+    }
+}
+
+fn format_sink_type(sink_type: &SinkType) -> String {
+    match sink_type {
+        SinkType::Custom(name) => format!("Custom:{}", name),
+        _ => sink_type.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::dump_graph;
+    use hakana_reflection_info::code_location::{FilePath, HPos};
+    use hakana_reflection_info::data_flow::graph::{DataFlowGraph, GraphKind, WholeProgramKind};
+    use hakana_reflection_info::data_flow::node::{DataFlowNode, DataFlowNodeId, DataFlowNodeKind};
+    use hakana_reflection_info::data_flow::path::{ArrayDataKind, PathKind};
+    use hakana_reflection_info::taint::{SinkType, SourceType};
+    use hakana_str::Interner;
+    use serde_json::Value;
+    use std::fs;
+
+    fn get_pos(file_path: FilePath, line: u32) -> HPos {
+        HPos {
+            file_path,
+            start_offset: 0,
+            end_offset: 0,
+            start_line: line,
+            end_line: line,
+            start_column: 5,
+            end_column: 10,
+        }
+    }
+
+    fn get_vertex(id: &str, pos: Option<HPos>) -> DataFlowNode {
+        DataFlowNode {
+            id: DataFlowNodeId::String(id.to_string()),
+            kind: DataFlowNodeKind::Vertex {
+                pos,
+                is_specialized: false,
+            },
+        }
+    }
+
+    // $get -> $arr -> $str -> sink in src/a.hack, and $other -> $other_copy in lib/b.hack
+    fn get_graph(interner: &mut Interner) -> DataFlowGraph {
+        let a_path = FilePath(interner.intern("/root/src/a.hack".to_string()));
+        let b_path = FilePath(interner.intern("/root/lib/b.hack".to_string()));
+
+        let mut graph = DataFlowGraph::new(GraphKind::WholeProgram(WholeProgramKind::Taint));
+
+        let source = DataFlowNode {
+            id: DataFlowNodeId::String("$get".to_string()),
+            kind: DataFlowNodeKind::TaintSource {
+                pos: Some(get_pos(a_path, 1)),
+                types: vec![SourceType::UriRequestHeader],
+            },
+        };
+        let arr = get_vertex("$arr", Some(get_pos(a_path, 2)));
+        let str = get_vertex("$str", Some(get_pos(a_path, 3)));
+        let sink = DataFlowNode {
+            id: DataFlowNodeId::String("sink".to_string()),
+            kind: DataFlowNodeKind::TaintSink {
+                pos: get_pos(a_path, 4),
+                types: vec![SinkType::Custom("log".to_string())],
+            },
+        };
+        let other = get_vertex("$other", Some(get_pos(b_path, 1)));
+        let other_copy = get_vertex("$other_copy", None);
+
+        graph.add_path(
+            &source,
+            &arr,
+            PathKind::ArrayAssignment(ArrayDataKind::ArrayValue, "a".to_string()),
+            vec![],
+            vec![],
+        );
+        graph.add_path(
+            &arr,
+            &str,
+            PathKind::ArrayFetch(ArrayDataKind::ArrayValue, "a".to_string()),
+            vec![SinkType::Custom("added".to_string())],
+            vec![],
+        );
+        graph.add_path(&str, &sink, PathKind::Default, vec![], vec![]);
+        graph.add_path(&other, &other_copy, PathKind::Default, vec![], vec![]);
+
+        for node in [source, arr, str, sink, other, other_copy] {
+            graph.add_node(node);
+        }
+
+        graph
+    }
+
+    fn dump_json(name: &str, around: Option<&str>, depth: usize) -> Value {
+        let mut interner = Interner::default();
+        let graph = get_graph(&mut interner);
+
+        let dir = std::env::temp_dir().join("hakana_graph_dump_test");
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join(format!("{}.json", name));
+
+        dump_graph(&graph, &interner, "/root", &path, around, depth).unwrap();
+
+        serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    fn get_node_ids(json: &Value) -> Vec<&str> {
+        json["nodes"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|node| node["id"].as_str().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn dumps_nodes_and_edges() {
+        let json = dump_json("all", None, 0);
+
+        assert_eq!(
+            get_node_ids(&json),
+            vec!["$arr", "$get", "$other", "$other_copy", "$str", "sink"]
+        );
+
+        let nodes = json["nodes"].as_array().unwrap();
+
+        assert_eq!(nodes[1]["kind"], "source");
+        assert_eq!(nodes[1]["file"], "src/a.hack");
+        assert_eq!(nodes[1]["line"], 1);
+        assert_eq!(nodes[1]["column"], 5);
+        assert_eq!(
+            nodes[1]["taints"][0],
+            SourceType::UriRequestHeader.to_string()
+        );
+
+        assert_eq!(nodes[3]["kind"], "vertex");
+        assert!(nodes[3].get("file").is_none());
+
+        assert_eq!(nodes[5]["kind"], "sink");
+        assert_eq!(nodes[5]["taints"][0], "Custom:log");
+
+        let edges = json["edges"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|edge| {
+                (
+                    edge["from"].as_str().unwrap(),
+                    edge["to"].as_str().unwrap(),
+                    edge["kind"].as_str().unwrap(),
+                )
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            edges,
+            vec![
+                ("$arr", "$str", "array-fetch"),
+                ("$get", "$arr", "array-assignment"),
+                ("$other", "$other_copy", ""),
+                ("$str", "sink", ""),
+            ]
+        );
+
+        assert_eq!(json["edges"][0]["added_taints"][0], "Custom:added");
+    }
+
+    #[test]
+    fn filters_around_symbol_by_depth() {
+        assert_eq!(
+            get_node_ids(&dump_json("depth0", Some("$arr"), 0)),
+            vec!["$arr"]
+        );
+
+        let json = dump_json("depth1", Some("$arr"), 1);
+
+        assert_eq!(get_node_ids(&json), vec!["$arr", "$get", "$str"]);
+        assert_eq!(json["edges"].as_array().unwrap().len(), 2);
+
+        assert_eq!(
+            get_node_ids(&dump_json("depth2", Some("$arr"), 2)),
+            vec!["$arr", "$get", "$str", "sink"]
+        );
+    }
+
+    #[test]
+    fn filters_around_file_or_directory() {
+        assert_eq!(
+            get_node_ids(&dump_json("file", Some("lib/b.hack"), 0)),
+            vec!["$other"]
+        );
+
+        assert_eq!(
+            get_node_ids(&dump_json("dir", Some("lib/"), 1)),
+            vec!["$other", "$other_copy"]
+        );
+
+        assert_eq!(
+            get_node_ids(&dump_json("src", Some("src"), 0)),
+            vec!["$arr", "$get", "$str", "sink"]
+        );
+    }
+}
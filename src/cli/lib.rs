@@ -1,16 +1,18 @@
 use clap::{arg, Command};
+use hakana_analyzer::config::query::QueryNode;
 use hakana_analyzer::config::{self};
 use hakana_analyzer::custom_hook::CustomHook;
 use hakana_logger::{Logger, Verbosity};
 use hakana_reflection_info::analysis_result::{
-    AnalysisResult, CheckPointEntry, FullEntry, HhClientEntry, Replacement,
+    AnalysisResult, CheckPointEntry, FullEntry, HhClientEntry, Replacement, TaintTraceEntry,
 };
 use hakana_reflection_info::data_flow::graph::{GraphKind, WholeProgramKind};
-use hakana_reflection_info::issue::IssueKind;
+use hakana_reflection_info::issue::{Issue, IssueKind, IssueSeverity};
 use hakana_str::Interner;
 use indexmap::IndexMap;
 use rand::Rng;
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
+use sarif::SarifLog;
 use std::collections::BTreeMap;
 use std::env;
 use std::fs::{self, File};
@@ -18,8 +20,12 @@ use std::io::Write;
 use std::path::Path;
 use std::process::exit;
 use std::sync::Arc;
+use std::time::Duration;
 use test_runners::test_runner::TestRunner;
 
+mod baseline;
+mod graph_dump;
+mod sarif;
 pub mod test_runners;
 
 pub fn init(
@@ -141,9 +147,21 @@ pub fn init(
                             .required(false)
                             .help("File to save output to"),
                     )
-                    .arg(arg!(--"json-format" <FORMAT>).required(false).help(
-                        "Format for JSON output. Options: checkpoint (default), full, hh_client",
-                    )),
+                    .arg(
+                        arg!(--"json-format" <FORMAT>).required(false).help(
+                            "JSON output format: checkpoint (default), full, hh_client or sarif",
+                        ),
+                    )
+                    .arg(
+                        arg!(--"set-baseline" <PATH>)
+                            .required(false)
+                            .help("Save all current issues to a baseline file"),
+                    )
+                    .arg(
+                        arg!(--"use-baseline" <PATH>)
+                            .required(false)
+                            .help("Only report issues that aren't in the given baseline file"),
+                    ),
             )
             .subcommand(
                 Command::new("migration-candidates")
@@ -362,16 +380,59 @@ pub fn init(
                             .required(false)
                             .help("Length of the longest allowable path"),
                     )
+                    .arg(
+                        arg!(--"max-nodes-per-source" <COUNT>)
+                            .required(false)
+                            .help("Most nodes to visit from a single source (default 100000)"),
+                    )
+                    .arg(
+                        arg!(--"time-budget" <SECONDS>)
+                            .required(false)
+                            .help("Stop looking for paths after this many seconds"),
+                    )
+                    .arg(arg!(--"report-truncated").required(false).help(
+                        "Report each source whose paths were cut short as an issue",
+                    ))
                     .arg(
                         arg!(--"debug")
                             .required(false)
                             .help("Add output for debugging"),
                     )
+                    .arg(
+                        arg!(--"no-cache")
+                            .required(false)
+                            .help("Whether to ignore the cache"),
+                    )
+                    .arg(
+                        arg!(--"diff")
+                            .required(false)
+                            .help("Only re-analyze code that changed since the last run"),
+                    )
                     .arg(
                         arg!(--"output" <PATH>)
                             .required(false)
                             .help("File to save output to"),
-                    ),
+                    )
+                    .arg(arg!(--"json-format" <FORMAT>).required(false).help(
+                        "JSON output format: checkpoint (default), full, hh_client or sarif",
+                    ))
+                    .arg(arg!(--"dump-graph" <PATH>).required(false).help(
+                        "File to write the dataflow graph to — JSON if it ends in .json, else DOT",
+                    ))
+                    .arg(arg!(--"dump-graph-around" <SYMBOL_OR_PATH>).required(false).help(
+                        "Only dump the graph around nodes mentioning this symbol or in this file",
+                    ))
+                    .arg(
+                        arg!(--"dump-graph-depth" <DEPTH>)
+                            .required(false)
+                            .help("How many edges away from --dump-graph-around to go (default 3)"),
+                    )
+                    .arg(arg!(--"compare-to" <PATH>).required(false).help(
+                        "A previous --output report — only new or changed findings are shown",
+                    ))
+                    .arg(arg!(--"fail-on-new-only").required(false).help(
+                        "With --compare-to, only fail if there are new or changed findings",
+                    )),
             )
             .subcommand(
                 Command::new("find-paths")
@@ -394,10 +455,65 @@ pub fn init(
                             .required(false)
                             .help("Length of the longest allowable path"),
                     )
+                    .arg(
+                        arg!(--"max-nodes-per-source" <COUNT>)
+                            .required(false)
+                            .help("Most nodes to visit from a single source (default 100000)"),
+                    )
+                    .arg(
+                        arg!(--"time-budget" <SECONDS>)
+                            .required(false)
+                            .help("Stop looking for paths after this many seconds"),
+                    )
+                    .arg(arg!(--"report-truncated").required(false).help(
+                        "Report each source whose paths were cut short as an issue",
+                    ))
                     .arg(
                         arg!(--"debug")
                             .required(false)
                             .help("Add output for debugging"),
+                    )
+                    .arg(
+                        arg!(--"source" <SOURCE>)
+                            .required(false)
+                            .multiple(true)
+                            .help("Treat <symbol>[:return|:param N|:property] as a data source"),
+                    )
+                    .arg(
+                        arg!(--"sink" <SINK>)
+                            .required(false)
+                            .multiple(true)
+                            .help("Report paths from --source to <symbol>:param N (N may be *)"),
+                    )
+                    .arg(arg!(--"dump-graph" <PATH>).required(false).help(
+                        "File to write the dataflow graph to — JSON if it ends in .json, else DOT",
+                    ))
+                    .arg(arg!(--"dump-graph-around" <SYMBOL_OR_PATH>).required(false).help(
+                        "Only dump the graph around nodes mentioning this symbol or in this file",
+                    ))
+                    .arg(
+                        arg!(--"dump-graph-depth" <DEPTH>)
+                            .required(false)
+                            .help("How many edges away from --dump-graph-around to go (default 3)"),
+                    ),
+            )
+            .subcommand(
+                Command::new("config")
+                    .about("Inspects the Hakana config")
+                    .subcommand_required(true)
+                    .subcommand(
+                        Command::new("validate")
+                            .about("Reports every problem with the Hakana config")
+                            .arg(
+                                arg!(--"root" <PATH>).required(false).help(
+                                    "Hakana root directory — defaults to the current directory",
+                                ),
+                            )
+                            .arg(
+                                arg!(--"config" <PATH>)
+                                    .required(false)
+                                    .help("Hakana config path — defaults to ./hakana.json"),
+                            ),
                     ),
             )
             .subcommand(
@@ -440,6 +556,14 @@ pub fn init(
 
     let cwd = (env::current_dir()).unwrap().to_str().unwrap().to_string();
 
+    if let Some(("config", config_matches)) = matches.subcommand() {
+        if let Some(("validate", sub_matches)) = config_matches.subcommand() {
+            do_validate_config(sub_matches, all_custom_issues, &cwd);
+        }
+
+        return;
+    }
+
     let threads = match matches.subcommand() {
         Some(("test", _)) => 1,
         Some((_, sub_matches)) => {
@@ -521,6 +645,7 @@ pub fn init(
                 &cwd,
                 all_custom_issues,
                 config_path,
+                cache_dir,
                 sub_matches,
                 analysis_hooks,
                 threads,
@@ -658,6 +783,39 @@ pub fn init(
     }
 }
 
+fn do_validate_config(
+    sub_matches: &clap::ArgMatches,
+    all_custom_issues: FxHashSet<String>,
+    cwd: &String,
+) {
+    let root_dir = sub_matches.value_of("root").unwrap_or(cwd.as_str());
+
+    let config_path = sub_matches
+        .value_of("config")
+        .map(|f| f.to_string())
+        .unwrap_or(format!("{}/hakana.json", root_dir));
+    let config_path = Path::new(&config_path);
+
+    if !config_path.exists() {
+        println!("Config file {} does not exist", config_path.display());
+        exit(1);
+    }
+
+    let mut config = config::Config::new(root_dir.to_string(), all_custom_issues);
+
+    update_config_from_file(&mut config, cwd, config_path);
+
+    println!("Config file {} is valid", config_path.display());
+}
+
+// exits rather than continuing with a partially-applied config
+fn update_config_from_file(config: &mut config::Config, cwd: &String, config_path: &Path) {
+    if let Err(error) = config.update_from_file(cwd, config_path) {
+        println!("Invalid config file {}:\n{}", config_path.display(), error);
+        exit(1);
+    }
+}
+
 fn do_fix(
     sub_matches: &clap::ArgMatches,
     all_custom_issues: FxHashSet<String>,
@@ -675,7 +833,7 @@ fn do_fix(
     let filter = sub_matches.value_of("filter").map(|f| f.to_string());
 
     let mut config = config::Config::new(root_dir.clone(), all_custom_issues);
-    config.hooks = analysis_hooks;
+    config.hooks = analysis_hooks.into_iter().map(Arc::from).collect();
 
     config.find_unused_expressions = issue_kind.is_unused_expression();
     config.find_unused_definitions = issue_kind.is_unused_definition();
@@ -684,10 +842,10 @@ fn do_fix(
     let config_path = config_path.unwrap();
 
     if config_path.exists() {
-        config.update_from_file(&cwd, config_path).ok();
+        update_config_from_file(&mut config, &cwd, config_path);
     }
 
-    config.allowed_issues = None;
+    config.override_allowed_issues(None);
 
     let result = hakana_workhorse::scan_and_analyze(
         Vec::new(),
@@ -727,14 +885,14 @@ fn do_remove_unused_fixmes(
 
     let mut config = config::Config::new(root_dir.clone(), all_custom_issues);
 
-    config.hooks = analysis_hooks;
+    config.hooks = analysis_hooks.into_iter().map(Arc::from).collect();
 
     let config_path = config_path.unwrap();
 
     if config_path.exists() {
-        config.update_from_file(cwd, config_path).ok();
+        update_config_from_file(&mut config, cwd, config_path);
     }
-    config.allowed_issues = None;
+    config.override_allowed_issues(None);
 
     config.find_unused_expressions = true;
     config.find_unused_definitions = true;
@@ -799,16 +957,16 @@ fn do_add_fixmes(
     let mut config = config::Config::new(root_dir.clone(), all_custom_issues);
 
     config.issues_to_fix.extend(issue_kinds_filter);
-    config.hooks = analysis_hooks;
+    config.hooks = analysis_hooks.into_iter().map(Arc::from).collect();
     config.find_unused_expressions = true;
     config.find_unused_definitions = true;
 
     let config_path = config_path.unwrap();
 
     if config_path.exists() {
-        config.update_from_file(cwd, config_path).ok();
+        update_config_from_file(&mut config, cwd, config_path);
     }
-    config.allowed_issues = None;
+    config.override_allowed_issues(None);
 
     config.add_fixmes = true;
 
@@ -859,6 +1017,7 @@ fn do_migrate(
                 false
             }
         })
+        .map(Arc::from)
         .collect();
 
     if config.hooks.is_empty() {
@@ -869,9 +1028,9 @@ fn do_migrate(
     let config_path = config_path.unwrap();
 
     if config_path.exists() {
-        config.update_from_file(cwd, config_path).ok();
+        update_config_from_file(&mut config, cwd, config_path);
     }
-    config.allowed_issues = None;
+    config.override_allowed_issues(None);
 
     let file_path = format!("{}/{}", cwd, migration_source);
 
@@ -943,6 +1102,7 @@ fn do_migration_candidates(
                 false
             }
         })
+        .map(Arc::from)
         .collect();
 
     config.in_migration = true;
@@ -955,9 +1115,9 @@ fn do_migration_candidates(
     let config_path = config_path.unwrap();
 
     if config_path.exists() {
-        config.update_from_file(cwd, config_path).ok();
+        update_config_from_file(&mut config, cwd, config_path);
     }
-    config.allowed_issues = None;
+    config.override_allowed_issues(None);
 
     let config = Arc::new(config);
 
@@ -1007,7 +1167,7 @@ fn do_codegen(
     let overwrite_codegen = sub_matches.is_present("overwrite");
 
     let mut config = config::Config::new(root_dir.to_string(), all_custom_issues);
-    config.hooks = analysis_hooks;
+    config.hooks = analysis_hooks.into_iter().map(Arc::from).collect();
     config.in_codegen = true;
 
     if let Some(codegen_name) = codegen_name {
@@ -1025,14 +1185,16 @@ fn do_codegen(
         }
     }
 
-    config.hooks.append(&mut codegen_hooks);
+    config
+        .hooks
+        .extend(codegen_hooks.into_iter().map(Arc::<dyn CustomHook>::from));
 
     let config_path = config_path.unwrap();
 
     if config_path.exists() {
-        config.update_from_file(cwd, config_path).ok();
+        update_config_from_file(&mut config, cwd, config_path);
     }
-    config.allowed_issues = None;
+    config.override_allowed_issues(None);
 
     let config = Arc::new(config);
 
@@ -1139,9 +1301,9 @@ fn do_find_paths(
     let config_path = config_path.unwrap();
 
     if config_path.exists() {
-        config.update_from_file(cwd, config_path).ok();
+        update_config_from_file(&mut config, cwd, config_path);
     }
-    config.allowed_issues = None;
+    config.override_allowed_issues(None);
 
     config.security_config.max_depth =
         if let Some(val) = sub_matches.value_of("max-depth").map(|f| f.to_string()) {
@@ -1150,7 +1312,21 @@ fn do_find_paths(
             20
         };
 
-    config.hooks = analysis_hooks;
+    set_taint_search_limits(&mut config, sub_matches);
+
+    config.security_config.query_sources =
+        parse_query_nodes(sub_matches, "source", QueryNode::parse_source);
+    config.security_config.query_sinks =
+        parse_query_nodes(sub_matches, "sink", QueryNode::parse_sink);
+
+    if config.security_config.query_sources.is_empty()
+        != config.security_config.query_sinks.is_empty()
+    {
+        println!("--source and --sink must be used together");
+        exit(1);
+    }
+
+    config.hooks = analysis_hooks.into_iter().map(Arc::from).collect();
 
     let root_dir = config.root_dir.clone();
 
@@ -1169,18 +1345,32 @@ fn do_find_paths(
     );
 
     if let Ok((analysis_result, successful_run_data)) = result {
+        dump_graph_if_requested(
+            sub_matches,
+            &analysis_result,
+            &successful_run_data.interner,
+            &root_dir,
+        );
+
+        let mut had_issues = false;
+
         for (file_path, issues) in
             analysis_result.get_all_issues(&successful_run_data.interner, &root_dir, true)
         {
             for issue in issues {
-                *had_error = true;
+                if issue.severity == IssueSeverity::Error {
+                    *had_error = true;
+                }
+                had_issues = true;
                 println!("{}", issue.format(&file_path));
             }
         }
 
-        if !*had_error {
+        if !had_issues {
             println!("\nNo security issues found!\n");
         }
+
+        print_truncated_taint_sources(&analysis_result);
     }
 }
 
@@ -1188,6 +1378,7 @@ fn do_security_check(
     cwd: &String,
     all_custom_issues: FxHashSet<String>,
     config_path: Option<&Path>,
+    cache_dir: String,
     sub_matches: &clap::ArgMatches,
     analysis_hooks: Vec<Box<dyn CustomHook>>,
     threads: u8,
@@ -1201,11 +1392,12 @@ fn do_security_check(
     let config_path = config_path.unwrap();
 
     if config_path.exists() {
-        config.update_from_file(cwd, config_path).ok();
+        update_config_from_file(&mut config, cwd, config_path);
     }
-    config.allowed_issues = None;
+    config.override_allowed_issues(None);
 
     let output_file = sub_matches.value_of("output").map(|f| f.to_string());
+    let output_format = sub_matches.value_of("json-format").map(|f| f.to_string());
 
     config.security_config.max_depth =
         if let Some(val) = sub_matches.value_of("max-depth").map(|f| f.to_string()) {
@@ -1214,16 +1406,45 @@ fn do_security_check(
             20
         };
 
-    config.hooks = analysis_hooks;
+    set_taint_search_limits(&mut config, sub_matches);
+
+    config.hooks = analysis_hooks.into_iter().map(Arc::from).collect();
+    config.ast_diff = sub_matches.is_present("diff");
+
+    // analysis results differ from those of analyze, so they're cached separately
+    let cache_dir = format!("{}/security", cache_dir);
+
+    if !Path::new(&cache_dir).is_dir() && fs::create_dir(&cache_dir).is_err() {
+        panic!("could not create security cache directory");
+    }
+
+    let previous_taint_traces = sub_matches.value_of("compare-to").map(|previous_path| {
+        match get_previous_taint_traces(previous_path) {
+            Ok(previous_taint_traces) => previous_taint_traces,
+            Err(error) => {
+                println!(
+                    "Could not read previous report {}: {}",
+                    previous_path, error
+                );
+                exit(1);
+            }
+        }
+    });
+    let fail_on_new_only = sub_matches.is_present("fail-on-new-only");
 
     let root_dir = config.root_dir.clone();
+    let config = Arc::new(config);
 
     let result = hakana_workhorse::scan_and_analyze(
         Vec::new(),
         None,
         None,
-        Arc::new(config),
-        None,
+        config.clone(),
+        if sub_matches.is_present("no-cache") {
+            None
+        } else {
+            Some(&cache_dir)
+        },
         threads,
         Arc::new(logger),
         header,
@@ -1233,26 +1454,70 @@ fn do_security_check(
     );
 
     if let Ok((analysis_result, successful_run_data)) = result {
+        dump_graph_if_requested(
+            sub_matches,
+            &analysis_result,
+            &successful_run_data.interner,
+            &root_dir,
+        );
+
+        let mut known_issue_count = 0;
+        let mut has_new_issues = false;
+
         for (file_path, issues) in
             analysis_result.get_all_issues(&successful_run_data.interner, &root_dir, true)
         {
             for issue in issues {
-                *had_error = true;
+                let is_known = if let Some(previous_taint_traces) = &previous_taint_traces {
+                    is_previously_reported(
+                        issue,
+                        previous_taint_traces,
+                        &successful_run_data.interner,
+                        cwd,
+                    )
+                } else {
+                    false
+                };
+
+                if fails_security_check(issue, is_known, fail_on_new_only) {
+                    *had_error = true;
+                }
+
+                if is_known {
+                    known_issue_count += 1;
+                    continue;
+                }
+
+                has_new_issues = true;
                 println!("{}", issue.format(&file_path));
             }
         }
 
-        if !*had_error {
+        if previous_taint_traces.is_some() {
+            if !has_new_issues {
+                println!("\nNo new security issues found!\n");
+            }
+
+            if known_issue_count > 0 {
+                println!(
+                    "{} previously-reported security issues not shown\n",
+                    known_issue_count
+                );
+            }
+        } else if !has_new_issues {
             println!("\nNo security issues found!\n");
         }
 
+        print_truncated_taint_sources(&analysis_result);
+
         if let Some(output_file) = output_file {
             write_output_files(
                 output_file,
-                None,
+                output_format,
                 cwd,
                 &analysis_result,
                 &successful_run_data.interner,
+                &config,
             );
         }
     }
@@ -1275,6 +1540,8 @@ fn do_analysis(
 
     let output_file = sub_matches.value_of("output").map(|f| f.to_string());
     let output_format = sub_matches.value_of("json-format").map(|f| f.to_string());
+    let set_baseline = sub_matches.value_of("set-baseline").map(|f| f.to_string());
+    let use_baseline = sub_matches.value_of("use-baseline").map(|f| f.to_string());
 
     let ignored = sub_matches
         .values_of("ignore")
@@ -1314,28 +1581,29 @@ fn do_analysis(
     config.ignore_mixed_issues = ignore_mixed_issues;
     config.ast_diff = do_ast_diff;
 
-    config.hooks = analysis_hooks;
+    config.hooks = analysis_hooks.into_iter().map(Arc::from).collect();
 
     let config_path = config_path.unwrap();
 
     if config_path.exists() {
-        config.update_from_file(cwd, config_path).ok();
+        update_config_from_file(&mut config, cwd, config_path);
     }
 
     // do this after we've loaded from file, as they can be overridden
     if !issue_kinds_filter.is_empty() {
-        config.allowed_issues = Some(issue_kinds_filter);
+        config.override_allowed_issues(Some(issue_kinds_filter));
     } else if show_all_issues {
-        config.allowed_issues = None;
+        config.override_allowed_issues(None);
     }
 
     let root_dir = config.root_dir.clone();
+    let config = Arc::new(config);
 
     let result = hakana_workhorse::scan_and_analyze(
         Vec::new(),
         filter,
         ignored,
-        Arc::new(config),
+        config.clone(),
         if sub_matches.is_present("no-cache") {
             None
         } else {
@@ -1349,17 +1617,83 @@ fn do_analysis(
         None,
     );
 
-    if let Ok((analysis_result, successful_run_data)) = result {
+    if let Ok((mut analysis_result, successful_run_data)) = result {
+        if let Some(set_baseline) = set_baseline {
+            match baseline::write_baseline(
+                &set_baseline,
+                &analysis_result,
+                &successful_run_data.interner,
+                &root_dir,
+            ) {
+                Ok(issue_count) => {
+                    println!("Saved {} issues to baseline {}", issue_count, set_baseline);
+                    return;
+                }
+                Err(error) => {
+                    println!("Could not write baseline {}: {}", set_baseline, error);
+                    exit(1);
+                }
+            }
+        }
+
+        let baseline_result = if let Some(use_baseline) = use_baseline {
+            match baseline::apply_baseline(
+                &use_baseline,
+                &mut analysis_result,
+                &successful_run_data.interner,
+                &root_dir,
+            ) {
+                Ok(baseline_result) => Some(baseline_result),
+                Err(error) => {
+                    println!("{}", error);
+                    exit(1);
+                }
+            }
+        } else {
+            None
+        };
+
+        // warnings and info-level issues don't fail the run, but are still reported
+        let mut had_issues = false;
+
         for (file_path, issues) in
             analysis_result.get_all_issues(&successful_run_data.interner, &root_dir, true)
         {
             for issue in issues {
-                *had_error = true;
+                if issue.severity == IssueSeverity::Error {
+                    *had_error = true;
+                }
+                had_issues = true;
                 println!("{}", issue.format(&file_path));
             }
         }
 
-        if !*had_error {
+        if let Some(baseline_result) = baseline_result {
+            println!(
+                "\n{} issues were suppressed by the baseline",
+                baseline_result.suppressed_count
+            );
+
+            if !baseline_result.stale_entries.is_empty() {
+                println!("\nThese baseline entries no longer match any issue and can be removed:");
+
+                for (key, count) in baseline_result.stale_entries {
+                    println!(
+                        "{} - {}{} ({}x)",
+                        key.kind,
+                        key.file,
+                        if key.symbol.is_empty() {
+                            "".to_string()
+                        } else {
+                            format!(" in {}", key.symbol)
+                        },
+                        count
+                    );
+                }
+            }
+        }
+
+        if !had_issues {
             println!("\nNo issues reported!\n");
         }
 
@@ -1370,6 +1704,7 @@ fn do_analysis(
                 cwd,
                 &analysis_result,
                 &successful_run_data.interner,
+                &config,
             );
         }
 
@@ -1409,12 +1744,189 @@ fn do_analysis(
     }
 }
 
+fn set_taint_search_limits(config: &mut config::Config, sub_matches: &clap::ArgMatches) {
+    if let Some(val) = sub_matches.value_of("max-nodes-per-source") {
+        if let Ok(max_nodes_per_source) = val.parse::<usize>() {
+            config.security_config.max_nodes_per_source = max_nodes_per_source;
+        } else {
+            println!(
+                "Invalid --max-nodes-per-source {} — expected a number of nodes",
+                val
+            );
+            exit(1);
+        }
+    }
+
+    if let Some(val) = sub_matches.value_of("time-budget") {
+        if let Ok(seconds) = val.parse::<u64>() {
+            config.security_config.time_budget = Some(Duration::from_secs(seconds));
+        } else {
+            println!(
+                "Invalid --time-budget {} — expected a number of seconds",
+                val
+            );
+            exit(1);
+        }
+    }
+
+    config.security_config.report_truncated_paths = sub_matches.is_present("report-truncated");
+}
+
+// a clean run only means something if every path was followed to its end
+fn print_truncated_taint_sources(analysis_result: &AnalysisResult) {
+    let truncated_taint_sources = &analysis_result.truncated_taint_sources;
+
+    if truncated_taint_sources.total() > 0 {
+        println!(
+            "Paths from {} sources were not fully explored:",
+            truncated_taint_sources.total()
+        );
+        println!(
+            "{} by --max-depth, {} by --max-nodes-per-source, {} by --time-budget\n",
+            truncated_taint_sources.by_depth,
+            truncated_taint_sources.by_node_budget,
+            truncated_taint_sources.by_time_budget,
+        );
+    }
+}
+
+fn parse_query_nodes(
+    sub_matches: &clap::ArgMatches,
+    arg_name: &str,
+    parse: fn(&str) -> Result<QueryNode, String>,
+) -> Vec<QueryNode> {
+    let mut query_nodes = vec![];
+
+    if let Some(values) = sub_matches.values_of(arg_name) {
+        for value in values {
+            match parse(value) {
+                Ok(query_node) => query_nodes.push(query_node),
+                Err(error) => {
+                    println!("{}", error);
+                    exit(1);
+                }
+            }
+        }
+    }
+
+    query_nodes
+}
+
+fn get_previous_taint_traces(
+    previous_path: &str,
+) -> Result<FxHashMap<String, serde_json::Value>, String> {
+    let contents = fs::read_to_string(previous_path).map_err(|error| error.to_string())?;
+
+    get_taint_traces_from_report(&contents)
+}
+
+// Maps the fingerprint of every TaintedData issue in a previous --output report to its trace
+pub(crate) fn get_taint_traces_from_report(
+    contents: &str,
+) -> Result<FxHashMap<String, serde_json::Value>, String> {
+    let entries = serde_json::from_str::<Vec<serde_json::Value>>(contents)
+        .map_err(|error| error.to_string())?;
+
+    let mut previous_taint_traces = FxHashMap::default();
+
+    for mut entry in entries {
+        if let Some(fingerprint) = entry.get("fingerprint").and_then(|f| f.as_str()) {
+            let fingerprint = fingerprint.to_string();
+            let taint_trace = entry
+                .get_mut("taint_trace")
+                .map(|t| t.take())
+                .unwrap_or_default();
+            previous_taint_traces.insert(fingerprint, taint_trace);
+        }
+    }
+
+    Ok(previous_taint_traces)
+}
+
+// An issue was previously reported if it has the same fingerprint and took the same path
+pub(crate) fn is_previously_reported(
+    issue: &Issue,
+    previous_taint_traces: &FxHashMap<String, serde_json::Value>,
+    interner: &Interner,
+    cwd: &str,
+) -> bool {
+    // paths in --output reports are relative to the current directory
+    let file_path = issue.pos.file_path.get_relative_path(interner, cwd);
+
+    if let Some(previous_taint_trace) = issue
+        .get_taint_fingerprint(&file_path)
+        .and_then(|fingerprint| previous_taint_traces.get(&fingerprint))
+    {
+        let taint_trace =
+            serde_json::to_value(TaintTraceEntry::from_issue(issue, interner, cwd)).unwrap();
+
+        get_taint_trace_hops(&taint_trace) == get_taint_trace_hops(previous_taint_trace)
+    } else {
+        false
+    }
+}
+
+// Hops are compared without their positions, which shift whenever code above them changes
+fn get_taint_trace_hops(
+    taint_trace: &serde_json::Value,
+) -> Vec<(&serde_json::Value, &serde_json::Value, &serde_json::Value)> {
+    taint_trace
+        .as_array()
+        .map(|hops| {
+            hops.iter()
+                .map(|hop| (&hop["label"], &hop["path_kind"], &hop["file_path"]))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// With --fail-on-new-only, errors that were already in the --compare-to report don't fail the run
+pub(crate) fn fails_security_check(issue: &Issue, is_known: bool, fail_on_new_only: bool) -> bool {
+    issue.severity == IssueSeverity::Error && !(is_known && fail_on_new_only)
+}
+
+fn dump_graph_if_requested(
+    sub_matches: &clap::ArgMatches,
+    analysis_result: &AnalysisResult,
+    interner: &Interner,
+    root_dir: &str,
+) {
+    if let Some(dump_graph_path) = sub_matches.value_of("dump-graph") {
+        let depth = if let Some(val) = sub_matches.value_of("dump-graph-depth") {
+            if let Ok(depth) = val.parse::<usize>() {
+                depth
+            } else {
+                println!(
+                    "Invalid --dump-graph-depth {} — expected a number of edges",
+                    val
+                );
+                exit(1);
+            }
+        } else {
+            3
+        };
+
+        if let Err(error) = graph_dump::dump_graph(
+            &analysis_result.program_dataflow_graph,
+            interner,
+            root_dir,
+            Path::new(dump_graph_path),
+            sub_matches.value_of("dump-graph-around"),
+            depth,
+        ) {
+            println!("Could not write graph to {}: {}", dump_graph_path, error);
+            exit(1);
+        }
+    }
+}
+
 fn write_output_files(
     output_file: String,
     output_format: Option<String>,
     cwd: &String,
     analysis_result: &AnalysisResult,
     interner: &Interner,
+    config: &config::Config,
 ) {
     let output_path = if output_file.starts_with('/') {
         output_file
@@ -1429,7 +1941,7 @@ fn write_output_files(
 
             for (file_path, issues) in analysis_result.get_all_issues(interner, cwd, true) {
                 for issue in issues {
-                    entries.push(FullEntry::from_issue(issue, &file_path));
+                    entries.push(FullEntry::from_issue(issue, &file_path, interner, cwd));
                 }
             }
 
@@ -1446,12 +1958,18 @@ fn write_output_files(
 
             serde_json::to_string_pretty(&entries).unwrap()
         }
+        Some(format) if format == "sarif" => serde_json::to_string_pretty(
+            &SarifLog::from_analysis_result(analysis_result, interner, cwd, config),
+        )
+        .unwrap(),
         _ => {
             let mut checkpoint_entries = vec![];
 
             for (file_path, issues) in analysis_result.get_all_issues(interner, cwd, true) {
                 for issue in issues {
-                    checkpoint_entries.push(CheckPointEntry::from_issue(issue, &file_path));
+                    checkpoint_entries.push(CheckPointEntry::from_issue(
+                        issue, &file_path, interner, cwd,
+                    ));
                 }
             }
 
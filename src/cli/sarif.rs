@@ -0,0 +1,419 @@
+use hakana_analyzer::config::Config;
+use hakana_reflection_info::analysis_result::AnalysisResult;
+use hakana_reflection_info::code_location::{FilePath, HPos};
+use hakana_reflection_info::issue::{Issue, IssueSeverity, TaintTraceStep};
+use hakana_str::Interner;
+use rustc_hash::FxHashMap;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+
+#[derive(Serialize)]
+pub struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<SarifRun>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRun {
+    tool: SarifTool,
+    results: Vec<SarifResult>,
+    column_kind: &'static str,
+}
+
+#[derive(Serialize)]
+struct SarifTool {
+    driver: SarifDriver,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifDriver {
+    name: &'static str,
+    information_uri: &'static str,
+    version: &'static str,
+    rules: Vec<SarifRule>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRule {
+    id: String,
+    name: String,
+    short_description: SarifMessage,
+    default_configuration: SarifConfiguration,
+}
+
+#[derive(Serialize)]
+struct SarifConfiguration {
+    level: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: String,
+    rule_index: usize,
+    level: &'static str,
+    message: SarifMessage,
+    locations: Vec<SarifLocation>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    code_flows: Vec<SarifCodeFlow>,
+}
+
+#[derive(Serialize)]
+struct SarifMessage {
+    text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifLocation {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    physical_location: Option<SarifPhysicalLocation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<SarifMessage>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifPhysicalLocation {
+    artifact_location: SarifArtifactLocation,
+    region: SarifRegion,
+}
+
+#[derive(Serialize)]
+struct SarifArtifactLocation {
+    uri: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRegion {
+    start_line: u32,
+    start_column: u16,
+    end_line: u32,
+    end_column: u16,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifCodeFlow {
+    thread_flows: Vec<SarifThreadFlow>,
+}
+
+#[derive(Serialize)]
+struct SarifThreadFlow {
+    locations: Vec<SarifThreadFlowLocation>,
+}
+
+#[derive(Serialize)]
+struct SarifThreadFlowLocation {
+    location: SarifLocation,
+}
+
+impl SarifLog {
+    pub fn from_analysis_result(
+        analysis_result: &AnalysisResult,
+        interner: &Interner,
+        root_dir: &str,
+        config: &Config,
+    ) -> Self {
+        let all_issues = analysis_result.get_all_issues(interner, root_dir, true);
+
+        // the severity configured for the whole codebase, which per-path settings can override
+        let rule_levels = all_issues
+            .values()
+            .flatten()
+            .map(|issue| {
+                (
+                    issue.kind.to_string(),
+                    get_level(
+                        config
+                            .issue_severities
+                            .get(&issue.kind)
+                            .copied()
+                            .unwrap_or_default(),
+                    ),
+                )
+            })
+            .collect::<BTreeMap<_, _>>();
+
+        // rules are sorted so that the output is stable between runs
+        let mut rule_indexes = all_issues
+            .values()
+            .flatten()
+            .map(|issue| (issue.kind.to_string(), 0))
+            .collect::<BTreeMap<_, _>>();
+
+        for (i, rule_index) in rule_indexes.values_mut().enumerate() {
+            *rule_index = i;
+        }
+
+        // SARIF columns count UTF-16 code units, so we need the contents of every file we point to
+        let mut file_contents = FxHashMap::default();
+
+        for issue in all_issues.values().flatten() {
+            let trace_positions = issue
+                .taint_trace
+                .iter()
+                .flatten()
+                .filter_map(|trace_step| trace_step.pos.as_ref());
+
+            for pos in std::iter::once(&issue.pos).chain(trace_positions) {
+                file_contents
+                    .entry(pos.file_path)
+                    .or_insert_with(|| fs::read_to_string(interner.lookup(&pos.file_path.0)).ok());
+            }
+        }
+
+        let mut results = vec![];
+
+        for (file_path, issues) in &all_issues {
+            for issue in issues {
+                results.push(SarifResult::from_issue(
+                    issue,
+                    file_path,
+                    rule_indexes[&issue.kind.to_string()],
+                    interner,
+                    root_dir,
+                    &file_contents,
+                ));
+            }
+        }
+
+        Self {
+            schema: SARIF_SCHEMA,
+            version: "2.1.0",
+            runs: vec![SarifRun {
+                tool: SarifTool {
+                    driver: SarifDriver {
+                        name: "Hakana",
+                        information_uri: "https://github.com/slackhq/hakana",
+                        version: env!("CARGO_PKG_VERSION"),
+                        rules: rule_indexes
+                            .into_keys()
+                            .map(|issue_kind| SarifRule {
+                                id: issue_kind.clone(),
+                                short_description: SarifMessage {
+                                    text: issue_kind.clone(),
+                                },
+                                default_configuration: SarifConfiguration {
+                                    level: rule_levels[&issue_kind],
+                                },
+                                name: issue_kind,
+                            })
+                            .collect(),
+                    },
+                },
+                results,
+                column_kind: "utf16CodeUnits",
+            }],
+        }
+    }
+}
+
+impl SarifResult {
+    fn from_issue(
+        issue: &Issue,
+        path: &str,
+        rule_index: usize,
+        interner: &Interner,
+        root_dir: &str,
+        file_contents: &FxHashMap<FilePath, Option<String>>,
+    ) -> Self {
+        Self {
+            rule_id: issue.kind.to_string(),
+            rule_index,
+            level: get_level(issue.severity),
+            message: SarifMessage {
+                text: issue.description.clone(),
+            },
+            locations: vec![SarifLocation {
+                physical_location: Some(SarifPhysicalLocation::from_pos(
+                    &issue.pos,
+                    path,
+                    file_contents,
+                )),
+                message: None,
+            }],
+            code_flows: if let Some(taint_trace) = &issue.taint_trace {
+                vec![SarifCodeFlow {
+                    thread_flows: vec![SarifThreadFlow {
+                        locations: taint_trace
+                            .iter()
+                            .map(|trace_step| SarifThreadFlowLocation {
+                                location: SarifLocation::from_trace_step(
+                                    trace_step,
+                                    interner,
+                                    root_dir,
+                                    file_contents,
+                                ),
+                            })
+                            .collect(),
+                    }],
+                }]
+            } else {
+                vec![]
+            },
+        }
+    }
+}
+
+impl SarifLocation {
+    fn from_trace_step(
+        trace_step: &TaintTraceStep,
+        interner: &Interner,
+        root_dir: &str,
+        file_contents: &FxHashMap<FilePath, Option<String>>,
+    ) -> Self {
+        Self {
+            physical_location: trace_step.pos.as_ref().map(|pos| {
+                SarifPhysicalLocation::from_pos(
+                    pos,
+                    &pos.file_path.get_relative_path(interner, root_dir),
+                    file_contents,
+                )
+            }),
+            message: Some(SarifMessage {
+                text: match &trace_step.path_kind {
+                    Some(path_kind) if !path_kind.to_string().is_empty() => {
+                        format!("{} (via {})", trace_step.label, path_kind)
+                    }
+                    _ => trace_step.label.clone(),
+                },
+            }),
+        }
+    }
+}
+
+impl SarifPhysicalLocation {
+    fn from_pos(
+        pos: &HPos,
+        path: &str,
+        file_contents: &FxHashMap<FilePath, Option<String>>,
+    ) -> Self {
+        let file_contents = file_contents.get(&pos.file_path).and_then(Option::as_deref);
+
+        Self {
+            artifact_location: SarifArtifactLocation {
+                uri: path.to_string(),
+            },
+            region: SarifRegion {
+                start_line: pos.start_line,
+                start_column: get_utf16_column(file_contents, pos.start_offset, pos.start_column),
+                end_line: pos.end_line,
+                end_column: get_utf16_column(file_contents, pos.end_offset, pos.end_column),
+            },
+        }
+    }
+}
+
+// HPos columns count bytes from 1, so the bytes before the offset on its line give the
+// UTF-16 column. If the file can't be read we fall back to the byte column.
+fn get_utf16_column(file_contents: Option<&str>, offset: u32, column: u16) -> u16 {
+    let offset = offset as usize;
+
+    file_contents
+        .and_then(|file_contents| {
+            file_contents.get(offset.checked_sub(column.saturating_sub(1) as usize)?..offset)
+        })
+        .map(|line_prefix| line_prefix.encode_utf16().count() as u16 + 1)
+        .unwrap_or(column)
+}
+
+fn get_level(severity: IssueSeverity) -> &'static str {
+    match severity {
+        IssueSeverity::Error => "error",
+        IssueSeverity::Warning => "warning",
+        IssueSeverity::Info => "note",
+        IssueSeverity::Off => "none",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SarifLog;
+    use hakana_analyzer::config::Config;
+    use hakana_reflection_info::analysis_result::AnalysisResult;
+    use hakana_reflection_info::code_location::{FilePath, HPos};
+    use hakana_reflection_info::data_flow::graph::GraphKind;
+    use hakana_reflection_info::issue::{Issue, IssueKind, TaintTraceStep};
+    use hakana_reflection_info::symbol_references::SymbolReferences;
+    use hakana_str::Interner;
+    use rustc_hash::FxHashSet;
+    use std::fs;
+
+    #[test]
+    fn columns_are_utf16_code_units() {
+        let root_dir = std::env::temp_dir().join("hakana_sarif_test");
+        fs::create_dir_all(&root_dir).unwrap();
+        let root_dir = root_dir.to_str().unwrap().to_string();
+        let path = format!("{}/input.hack", root_dir);
+
+        // é is two bytes and one code unit, 😀 is four bytes and two code units
+        fs::write(&path, "<?hh\n$s = \"é😀\"; $x = 1;\n").unwrap();
+
+        let mut interner = Interner::default();
+        let file_path = FilePath(interner.intern(path));
+
+        // $x starts at byte column 16 and ends at byte column 18
+        let pos = HPos {
+            file_path,
+            start_offset: 20,
+            end_offset: 22,
+            start_line: 2,
+            end_line: 2,
+            start_column: 16,
+            end_column: 18,
+        };
+
+        let mut issue = Issue::new(
+            IssueKind::UnusedAssignment,
+            "Assignment to $x is unused".to_string(),
+            pos,
+            &None,
+        );
+        issue.taint_trace = Some(vec![TaintTraceStep {
+            label: "$x".to_string(),
+            pos: Some(pos),
+            path_kind: None,
+        }]);
+
+        let mut analysis_result =
+            AnalysisResult::new(GraphKind::FunctionBody, SymbolReferences::new());
+        analysis_result
+            .emitted_issues
+            .insert(file_path, vec![issue]);
+
+        let config = Config::new(root_dir.clone(), FxHashSet::default());
+
+        let sarif = serde_json::to_value(SarifLog::from_analysis_result(
+            &analysis_result,
+            &interner,
+            &root_dir,
+            &config,
+        ))
+        .unwrap();
+
+        let run = &sarif["runs"][0];
+        assert_eq!(run["columnKind"], "utf16CodeUnits");
+
+        let result = &run["results"][0];
+        let location = &result["locations"][0]["physicalLocation"];
+        assert_eq!(location["artifactLocation"]["uri"], "input.hack");
+        assert_eq!(location["region"]["startLine"], 2);
+        assert_eq!(location["region"]["startColumn"], 13);
+        assert_eq!(location["region"]["endColumn"], 15);
+
+        let trace_region = &result["codeFlows"][0]["threadFlows"][0]["locations"][0]["location"]
+            ["physicalLocation"]["region"];
+        assert_eq!(trace_region["startColumn"], 13);
+        assert_eq!(trace_region["endColumn"], 15);
+    }
+}
@@ -1,7 +1,8 @@
 use hakana_analyzer::config;
+use hakana_analyzer::config::query::QueryNode;
 use hakana_analyzer::custom_hook::CustomHook;
 use hakana_logger::Logger;
-use hakana_reflection_info::analysis_result::AnalysisResult;
+use hakana_reflection_info::analysis_result::{AnalysisResult, FullEntry};
 use hakana_reflection_info::code_location::FilePath;
 use hakana_reflection_info::data_flow::graph::GraphKind;
 use hakana_reflection_info::data_flow::graph::WholeProgramKind;
@@ -12,6 +13,7 @@ use rand::seq::SliceRandom;
 use rand::SeedableRng;
 use rustc_hash::FxHashMap;
 use rustc_hash::FxHashSet;
+use serde::Deserialize;
 
 use std::env;
 use std::fs;
@@ -28,6 +30,20 @@ pub trait HooksProvider {
 
 pub struct TestRunner(pub Box<dyn HooksProvider>);
 
+#[derive(Deserialize)]
+struct TestSecurityArgs {
+    #[serde(default)]
+    sources: Vec<String>,
+    #[serde(default)]
+    sinks: Vec<String>,
+    max_depth: Option<u8>,
+    #[serde(default)]
+    report_truncated_paths: bool,
+    // used by compare-to tests
+    #[serde(default)]
+    fail_on_new_only: bool,
+}
+
 impl TestRunner {
     pub fn run_test(
         &self,
@@ -142,7 +158,12 @@ impl TestRunner {
             GraphKind::FunctionBody
         };
 
-        analysis_config.hooks = self.0.get_hooks_for_test(dir);
+        analysis_config.hooks = self
+            .0
+            .get_hooks_for_test(dir)
+            .into_iter()
+            .map(Arc::from)
+            .collect();
 
         let mut dir_parts = dir.split('/').collect::<Vec<_>>();
 
@@ -184,6 +205,52 @@ impl TestRunner {
         } else if dir.contains("/migration-candidates/") {
             analysis_config.in_migration = true;
         }
+
+        let config_path = dir.to_string() + "/hakana.json";
+
+        if Path::new(&config_path).exists() {
+            analysis_config
+                .update_from_file(&dir.to_string(), Path::new(&config_path))
+                .unwrap();
+        }
+
+        let taint_spec_path = dir.to_string() + "/taint_spec.json";
+
+        if Path::new(&taint_spec_path).exists() {
+            let (taint_spec, errors) =
+                config::taint_spec::read_from_file(Path::new(&taint_spec_path)).unwrap();
+
+            // a spec with errors would make the test pass without exercising it
+            assert!(
+                errors.is_empty(),
+                "invalid {}:\n{}",
+                taint_spec_path,
+                errors.join("\n")
+            );
+
+            analysis_config.taint_spec.extend(taint_spec);
+        }
+
+        if let Some(security_args) = get_security_args_for_test(dir) {
+            analysis_config.security_config.query_sources = security_args
+                .sources
+                .iter()
+                .map(|source| QueryNode::parse_source(source).unwrap())
+                .collect();
+            analysis_config.security_config.query_sinks = security_args
+                .sinks
+                .iter()
+                .map(|sink| QueryNode::parse_sink(sink).unwrap())
+                .collect();
+
+            if let Some(max_depth) = security_args.max_depth {
+                analysis_config.security_config.max_depth = max_depth;
+            }
+
+            analysis_config.security_config.report_truncated_paths =
+                security_args.report_truncated_paths;
+        }
+
         analysis_config
     }
 
@@ -218,6 +285,16 @@ impl TestRunner {
             );
         }
 
+        if dir.contains("/compare-to/") {
+            return self.run_compare_to_test(
+                dir,
+                logger,
+                had_error,
+                test_diagnostics,
+                build_checksum,
+            );
+        }
+
         let cwd = env::current_dir().unwrap().to_str().unwrap().to_string();
 
         let analysis_config = self.get_config_for_test(&dir);
@@ -554,6 +631,143 @@ impl TestRunner {
             ("F".to_string(), Some(run_data), Some(analysis_result))
         }
     }
+
+    // Analyzes a/ and then b/ from scratch, and checks what `security-check --compare-to`
+    // would report for b/ given the --output report of a/, followed by its exit code
+    fn run_compare_to_test(
+        &self,
+        dir: String,
+        logger: Arc<Logger>,
+        had_error: &mut bool,
+        test_diagnostics: &mut Vec<(String, String)>,
+        build_checksum: &str,
+    ) -> (String, Option<SuccessfulScanData>, Option<AnalysisResult>) {
+        let cwd = env::current_dir().unwrap().to_str().unwrap().to_string();
+
+        logger.log_debug_sync(&format!("running test {}", dir));
+
+        let workdir_base = dir.clone() + "/workdir";
+
+        let config = Arc::new(self.get_config_for_test(&workdir_base));
+
+        let fail_on_new_only = get_security_args_for_test(&dir)
+            .map(|security_args| security_args.fail_on_new_only)
+            .unwrap_or(false);
+
+        let mut previous_report = None;
+        let mut run_result = None;
+
+        for folder in [dir.clone() + "/a", dir.clone() + "/b"] {
+            copy_recursively(folder, workdir_base.clone()).unwrap();
+
+            let result = hakana_workhorse::scan_and_analyze(
+                vec![cwd.clone() + "/tests/stubs"],
+                None,
+                Some(FxHashSet::from_iter(["tests/stubs/stubs.hack".to_string()])),
+                config.clone(),
+                None,
+                1,
+                logger.clone(),
+                build_checksum,
+                None,
+                None,
+                None,
+            );
+
+            fs::remove_dir_all(&workdir_base).unwrap();
+
+            match result {
+                Ok((analysis_result, run_data)) => {
+                    if previous_report.is_none() {
+                        let mut entries = vec![];
+
+                        for (file_path, issues) in
+                            analysis_result.get_all_issues(&run_data.interner, &cwd, true)
+                        {
+                            for issue in issues {
+                                entries.push(FullEntry::from_issue(
+                                    issue,
+                                    &file_path,
+                                    &run_data.interner,
+                                    &cwd,
+                                ));
+                            }
+                        }
+
+                        previous_report = Some(serde_json::to_string(&entries).unwrap());
+                    } else {
+                        run_result = Some((analysis_result, run_data));
+                    }
+                }
+                Err(error) => {
+                    *had_error = true;
+                    test_diagnostics.push((dir, error.to_string()));
+                    return ("F".to_string(), None, None);
+                }
+            }
+        }
+
+        let previous_taint_traces =
+            crate::get_taint_traces_from_report(&previous_report.unwrap()).unwrap();
+        let (analysis_result, run_data) = run_result.unwrap();
+
+        let mut test_output = vec![];
+        let mut known_issue_count = 0;
+        let mut fails = false;
+
+        for (file_path, issues) in
+            analysis_result.get_all_issues(&run_data.interner, &workdir_base, true)
+        {
+            for issue in issues {
+                let is_known = crate::is_previously_reported(
+                    issue,
+                    &previous_taint_traces,
+                    &run_data.interner,
+                    &cwd,
+                );
+
+                fails = fails || crate::fails_security_check(issue, is_known, fail_on_new_only);
+
+                if is_known {
+                    known_issue_count += 1;
+                } else {
+                    test_output.push(issue.format(&file_path));
+                }
+            }
+        }
+
+        if known_issue_count > 0 {
+            test_output.push(format!(
+                "{} previously-reported security issues not shown\n",
+                known_issue_count
+            ));
+        }
+
+        test_output.push(format!("exit code: {}\n", if fails { 1 } else { 0 }));
+
+        let expected_output = fs::read_to_string(dir.clone() + "/output.txt").unwrap();
+
+        if expected_output.trim() == test_output.join("").trim() {
+            (".".to_string(), Some(run_data), Some(analysis_result))
+        } else {
+            test_diagnostics.push((
+                dir,
+                format!("- {}\n+ {}", expected_output.trim(), test_output.join("+ ")),
+            ));
+            ("F".to_string(), Some(run_data), Some(analysis_result))
+        }
+    }
+}
+
+// security tests give their command line arguments in security_args.json
+fn get_security_args_for_test(dir: &str) -> Option<TestSecurityArgs> {
+    let security_args_path = dir.to_string() + "/security_args.json";
+
+    if Path::new(&security_args_path).exists() {
+        Some(serde_json::from_str(&fs::read_to_string(security_args_path).unwrap()).unwrap())
+    } else {
+        None
+    }
 }
 
 fn copy_recursively(source: impl AsRef<Path>, destination: impl AsRef<Path>) -> io::Result<()> {
@@ -590,7 +804,8 @@ fn get_all_test_folders(test_or_test_dir: String) -> Vec<String> {
             if metadata.is_dir() {
                 if let Some(path) = path.to_str() {
                     if (Path::new(&(path.to_owned() + "/input.hack")).exists()
-                        && !path.contains("/diff/"))
+                        && !path.contains("/diff/")
+                        && !path.contains("/compare-to/"))
                         || Path::new(&(path.to_owned() + "/output.txt")).exists()
                     {
                         test_folders.push(path.to_owned().to_string());
@@ -1,6 +1,6 @@
 use std::{collections::BTreeMap, time::Duration};
 
-use hakana_str::Interner;
+use hakana_str::{Interner, StrId};
 use rustc_hash::{FxHashMap, FxHashSet};
 use serde::Serialize;
 
@@ -8,10 +8,25 @@ use crate::{
     code_location::FilePath,
     data_flow::{graph::{DataFlowGraph, GraphKind}, node::DataFlowNodeId},
     function_context::FunctionLikeIdentifier,
-    issue::{Issue, IssueKind},
+    issue::{Issue, IssueKind, IssueSeverity, TaintTraceStep},
     symbol_references::SymbolReferences,
+    t_union::TUnion,
 };
 
+// how many sources had the search for their paths cut short, by the limit that stopped it
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TruncatedTaintSources {
+    pub by_depth: usize,
+    pub by_node_budget: usize,
+    pub by_time_budget: usize,
+}
+
+impl TruncatedTaintSources {
+    pub fn total(&self) -> usize {
+        self.by_depth + self.by_node_budget + self.by_time_budget
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum Replacement {
     Remove,
@@ -29,11 +44,18 @@ pub struct AnalysisResult {
     pub codegen: BTreeMap<String, Result<String, String>>,
     pub mixed_source_counts: FxHashMap<DataFlowNodeId, FxHashSet<String>>,
     pub program_dataflow_graph: DataFlowGraph,
+    // whole-program graph contributions, keyed by the symbol whose analysis produced them
+    pub symbol_dataflow_graphs: FxHashMap<(StrId, StrId), DataFlowGraph>,
     pub symbol_references: SymbolReferences,
     pub issue_counts: FxHashMap<IssueKind, usize>,
     pub time_in_analysis: Duration,
     pub functions_to_migrate: FxHashMap<FunctionLikeIdentifier, bool>,
     pub has_invalid_hack_files: bool,
+    pub truncated_taint_sources: TruncatedTaintSources,
+
+    // only populated for the language server, keyed by expression offsets
+    pub expr_types: FxHashMap<FilePath, FxHashMap<(u32, u32), TUnion>>,
+    pub symbol_uses: FxHashMap<FilePath, FxHashMap<(u32, u32), (StrId, StrId)>>,
 }
 
 impl AnalysisResult {
@@ -48,12 +70,16 @@ impl AnalysisResult {
             insertions: FxHashMap::default(),
             mixed_source_counts: FxHashMap::default(),
             program_dataflow_graph: DataFlowGraph::new(program_dataflow_graph_kind),
+            symbol_dataflow_graphs: FxHashMap::default(),
             issue_counts: FxHashMap::default(),
             symbol_references,
             time_in_analysis: Duration::default(),
             functions_to_migrate: FxHashMap::default(),
             codegen: BTreeMap::default(),
             has_invalid_hack_files: false,
+            truncated_taint_sources: TruncatedTaintSources::default(),
+            expr_types: FxHashMap::default(),
+            symbol_uses: FxHashMap::default(),
         }
     }
 
@@ -71,6 +97,13 @@ impl AnalysisResult {
         }
         self.program_dataflow_graph
             .add_graph(other.program_dataflow_graph);
+        for (symbol, graph) in other.symbol_dataflow_graphs {
+            if let Some(existing_graph) = self.symbol_dataflow_graphs.get_mut(&symbol) {
+                existing_graph.add_graph(graph);
+            } else {
+                self.symbol_dataflow_graphs.insert(symbol, graph);
+            }
+        }
         self.symbol_references.extend(other.symbol_references);
         for (kind, count) in other.issue_counts {
             *self.issue_counts.entry(kind).or_insert(0) += count;
@@ -78,6 +111,32 @@ impl AnalysisResult {
         self.functions_to_migrate.extend(other.functions_to_migrate);
         self.codegen.extend(other.codegen);
         self.has_invalid_hack_files = self.has_invalid_hack_files || other.has_invalid_hack_files;
+        for (file_path, expr_types) in other.expr_types {
+            self.expr_types
+                .entry(file_path)
+                .or_default()
+                .extend(expr_types);
+        }
+        for (file_path, symbol_uses) in other.symbol_uses {
+            self.symbol_uses
+                .entry(file_path)
+                .or_default()
+                .extend(symbol_uses);
+        }
+    }
+
+    // merges every symbol's contribution into the graph that paths are found in. The
+    // contributions are only kept when a later diff-based run needs to reuse them from memory
+    pub fn build_program_dataflow_graph(&mut self, keep_symbol_graphs: bool) {
+        if keep_symbol_graphs {
+            for graph in self.symbol_dataflow_graphs.values() {
+                self.program_dataflow_graph.add_graph(graph.clone());
+            }
+        } else {
+            for (_, graph) in self.symbol_dataflow_graphs.drain() {
+                self.program_dataflow_graph.add_graph(graph);
+            }
+        }
     }
 
     pub fn get_all_issues(
@@ -130,6 +189,7 @@ impl AnalysisResult {
 #[derive(Serialize)]
 pub struct FullEntry {
     pub kind: String,
+    pub severity: String,
     pub description: String,
     pub file_path: String,
     pub start_offset: u32,
@@ -138,12 +198,17 @@ pub struct FullEntry {
     pub end_offset: u32,
     pub end_line: u32,
     pub end_column: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub taint_trace: Option<Vec<TaintTraceEntry>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<String>,
 }
 
 impl FullEntry {
-    pub fn from_issue(issue: &Issue, path: &str) -> Self {
+    pub fn from_issue(issue: &Issue, path: &str, interner: &Interner, root_dir: &str) -> Self {
         Self {
             kind: issue.kind.to_string(),
+            severity: issue.severity.to_string(),
             description: issue.description.clone(),
             file_path: path.to_string(),
             start_offset: issue.pos.start_offset,
@@ -152,6 +217,8 @@ impl FullEntry {
             end_offset: issue.pos.end_offset,
             end_line: issue.pos.end_line,
             end_column: issue.pos.end_column,
+            taint_trace: TaintTraceEntry::from_issue(issue, interner, root_dir),
+            fingerprint: issue.get_taint_fingerprint(path),
         }
     }
 }
@@ -163,16 +230,75 @@ pub struct CheckPointEntry {
     pub filename: String,
     pub line: u32,
     pub output: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub taint_trace: Option<Vec<TaintTraceEntry>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<String>,
 }
 
 impl CheckPointEntry {
-    pub fn from_issue(issue: &Issue, path: &str) -> Self {
+    pub fn from_issue(issue: &Issue, path: &str, interner: &Interner, root_dir: &str) -> Self {
         Self {
             output: issue.description.clone(),
-            level: "failure".to_string(),
+            level: match issue.severity {
+                IssueSeverity::Warning => "warning".to_string(),
+                IssueSeverity::Info => "info".to_string(),
+                IssueSeverity::Error | IssueSeverity::Off => "failure".to_string(),
+            },
             filename: path.to_string(),
             line: issue.pos.start_line,
             case: issue.kind.to_string(),
+            taint_trace: TaintTraceEntry::from_issue(issue, interner, root_dir),
+            fingerprint: issue.get_taint_fingerprint(path),
+        }
+    }
+}
+
+#[derive(Serialize)]
+pub struct TaintTraceEntry {
+    pub label: String,
+    pub file_path: Option<String>,
+    pub start_offset: Option<u32>,
+    pub start_line: Option<u32>,
+    pub start_column: Option<u16>,
+    pub end_offset: Option<u32>,
+    pub end_line: Option<u32>,
+    pub end_column: Option<u16>,
+    pub path_kind: Option<String>,
+}
+
+impl TaintTraceEntry {
+    pub fn from_issue(issue: &Issue, interner: &Interner, root_dir: &str) -> Option<Vec<Self>> {
+        issue.taint_trace.as_ref().map(|taint_trace| {
+            taint_trace
+                .iter()
+                .map(|trace_step| Self::from_trace_step(trace_step, interner, root_dir))
+                .collect()
+        })
+    }
+
+    pub fn from_trace_step(
+        trace_step: &TaintTraceStep,
+        interner: &Interner,
+        root_dir: &str,
+    ) -> Self {
+        let pos = trace_step.pos.as_ref();
+
+        Self {
+            label: trace_step.label.clone(),
+            file_path: pos.map(|pos| pos.file_path.get_relative_path(interner, root_dir)),
+            start_offset: pos.map(|pos| pos.start_offset),
+            start_line: pos.map(|pos| pos.start_line),
+            start_column: pos.map(|pos| pos.start_column),
+            end_offset: pos.map(|pos| pos.end_offset),
+            end_line: pos.map(|pos| pos.end_line),
+            end_column: pos.map(|pos| pos.end_column),
+            // the default path kind has no label
+            path_kind: trace_step
+                .path_kind
+                .as_ref()
+                .map(|path_kind| path_kind.to_string())
+                .filter(|path_kind| !path_kind.is_empty()),
         }
     }
 }
@@ -185,6 +311,7 @@ pub struct HhClientEntry {
     pub start: u32,
     pub end: u32,
     pub code: String,
+    pub severity: String,
 }
 
 impl HhClientEntry {
@@ -196,6 +323,136 @@ impl HhClientEntry {
             start: issue.pos.start_column as u32,
             end: (issue.pos.end_offset - issue.pos.start_offset) + (issue.pos.start_column as u32),
             code: issue.kind.to_string(),
+            severity: issue.severity.to_string(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::FullEntry;
+    use crate::code_location::{FilePath, HPos};
+    use crate::data_flow::path::{ArrayDataKind, PathKind};
+    use crate::issue::{Issue, IssueKind, TaintTraceStep};
+    use crate::taint::SinkType;
+    use hakana_str::Interner;
+    use serde_json::json;
+
+    fn get_pos(file_path: FilePath, line: u32, column: u16, width: u16) -> HPos {
+        HPos {
+            file_path,
+            start_offset: line * 100 + column as u32,
+            end_offset: line * 100 + (column + width) as u32,
+            start_line: line,
+            end_line: line,
+            start_column: column,
+            end_column: column + width,
+        }
+    }
+
+    #[test]
+    fn serializes_taint_trace() {
+        let mut interner = Interner::default();
+        let file_path = FilePath(interner.intern("/root/src/a.hack".to_string()));
+
+        let mut issue = Issue::new(
+            IssueKind::TaintedData(Box::new(SinkType::HtmlTag)),
+            "Data from a URL query string found its way to echo".to_string(),
+            get_pos(file_path, 4, 10, 2),
+            &None,
+        );
+        issue.taint_source_id = Some("$_GET".to_string());
+        issue.taint_trace = Some(vec![
+            TaintTraceStep {
+                label: "$_GET".to_string(),
+                pos: Some(get_pos(file_path, 2, 10, 5)),
+                path_kind: None,
+            },
+            TaintTraceStep {
+                label: "$a".to_string(),
+                pos: Some(get_pos(file_path, 2, 5, 2)),
+                path_kind: Some(PathKind::ArrayFetch(
+                    ArrayDataKind::ArrayValue,
+                    "a".to_string(),
+                )),
+            },
+            TaintTraceStep {
+                label: "echo".to_string(),
+                pos: None,
+                path_kind: Some(PathKind::Default),
+            },
+        ]);
+
+        let entry = serde_json::to_value(FullEntry::from_issue(
+            &issue,
+            "src/a.hack",
+            &interner,
+            "/root",
+        ))
+        .unwrap();
+
+        assert_eq!(entry["fingerprint"], "$_GET|HtmlTag|src/a.hack:4:10");
+        assert_eq!(
+            entry["taint_trace"],
+            json!([
+                {
+                    "label": "$_GET",
+                    "file_path": "src/a.hack",
+                    "start_offset": 210,
+                    "start_line": 2,
+                    "start_column": 10,
+                    "end_offset": 215,
+                    "end_line": 2,
+                    "end_column": 15,
+                    "path_kind": null
+                },
+                {
+                    "label": "$a",
+                    "file_path": "src/a.hack",
+                    "start_offset": 205,
+                    "start_line": 2,
+                    "start_column": 5,
+                    "end_offset": 207,
+                    "end_line": 2,
+                    "end_column": 7,
+                    "path_kind": "array-fetch"
+                },
+                {
+                    "label": "echo",
+                    "file_path": null,
+                    "start_offset": null,
+                    "start_line": null,
+                    "start_column": null,
+                    "end_offset": null,
+                    "end_line": null,
+                    "end_column": null,
+                    "path_kind": null
+                }
+            ])
+        );
+    }
+
+    #[test]
+    fn omits_taint_trace_without_one() {
+        let mut interner = Interner::default();
+        let file_path = FilePath(interner.intern("/root/src/a.hack".to_string()));
+
+        let issue = Issue::new(
+            IssueKind::UnusedAssignment,
+            "Assignment to $a is unused".to_string(),
+            get_pos(file_path, 2, 5, 2),
+            &None,
+        );
+
+        let entry = serde_json::to_value(FullEntry::from_issue(
+            &issue,
+            "src/a.hack",
+            &interner,
+            "/root",
+        ))
+        .unwrap();
+
+        assert!(entry.get("taint_trace").is_none());
+        assert!(entry.get("fingerprint").is_none());
+    }
+}
@@ -17,4 +17,6 @@ pub struct ConstantInfo {
     pub unresolved_value: Option<UnresolvedConstantComponent>,
 
     pub is_abstract: bool,
+
+    pub deprecated: Option<String>,
 }
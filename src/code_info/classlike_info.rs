@@ -35,7 +35,8 @@ pub struct ClassLikeInfo {
 
     pub is_stubbed: bool,
 
-    pub is_deprecated: bool,
+    // the message given to __Deprecated, if the class is deprecated
+    pub deprecated: Option<String>,
 
     pub internal_to: Option<String>,
 
@@ -67,7 +68,7 @@ pub struct ClassLikeInfo {
     pub direct_parent_class: Option<StrId>,
 
     /**
-     * A trait can require extending classes and interfaces
+     * A trait can require extending classes and interfaces, or require a specific class
      */
     pub required_classlikes: Vec<StrId>,
 
@@ -218,7 +219,7 @@ impl ClassLikeInfo {
             constants: IndexMap::new(),
             is_populated: false,
             is_stubbed: false,
-            is_deprecated: false,
+            deprecated: None,
             is_abstract: false,
             is_final: false,
             kind: SymbolKind::Class,
@@ -5,20 +5,21 @@ use super::{
 use crate::{code_location::FilePath, taint::SinkType};
 use oxidized::ast_defs::Pos;
 use rustc_hash::{FxHashMap, FxHashSet};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum WholeProgramKind {
     Taint,
     Query,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GraphKind {
     FunctionBody,
     WholeProgram(WholeProgramKind),
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DataFlowGraph {
     pub kind: GraphKind,
     pub vertices: FxHashMap<DataFlowNodeId, DataFlowNode>,
@@ -50,6 +50,8 @@ pub enum DataFlowNodeId {
     Symbol(StrId),
     ShapeFieldAccess(StrId, String),
     InstanceMethodCall(FilePath, u32, u32),
+    // data written by StoresTaint methods and read by LoadsTaint methods, keyed by channel
+    TaintStorage(String),
 }
 
 impl DataFlowNodeId {
@@ -224,6 +226,7 @@ impl DataFlowNodeId {
                 "instance-method-call-{}:{}-{}",
                 file_path.0 .0, start_offset, end_offset
             ),
+            DataFlowNodeId::TaintStorage(channel) => format!("taint-storage-{}", channel),
         }
     }
 
@@ -297,6 +300,7 @@ impl DataFlowNodeId {
             }
             DataFlowNodeId::UnlabelledSink(..) => panic!(),
             DataFlowNodeId::InstanceMethodCall(..) => "instance method call".to_string(),
+            DataFlowNodeId::TaintStorage(channel) => format!("{} storage", channel),
         }
     }
 
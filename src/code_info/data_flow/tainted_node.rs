@@ -12,7 +12,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     code_location::{FilePath, HPos},
-    taint::{self, SinkType, SourceType},
+    issue::TaintTraceStep,
+    taint::{SinkType, SourceType, TaintPolicy},
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -61,6 +62,26 @@ impl TaintedNode {
         source_descriptor
     }
 
+    pub fn get_trace_steps(&self, interner: &Interner) -> Vec<TaintTraceStep> {
+        let mut trace_steps = if let Some(previous_source) = &self.previous {
+            previous_source.get_trace_steps(interner)
+        } else {
+            vec![]
+        };
+
+        trace_steps.push(TaintTraceStep {
+            label: self.id.to_label(interner),
+            pos: self.pos.as_ref().map(|pos| **pos),
+            path_kind: if self.previous.is_some() {
+                self.path_types.last().cloned()
+            } else {
+                None
+            },
+        });
+
+        trace_steps
+    }
+
     pub fn get_taint_sources(&self) -> &Vec<SourceType> {
         if let Some(previous_source) = &self.previous {
             return previous_source.get_taint_sources();
@@ -69,7 +90,15 @@ impl TaintedNode {
         &self.taint_sources
     }
 
-    pub fn from(node: &DataFlowNode) -> Self {
+    pub fn get_source(&self) -> &TaintedNode {
+        if let Some(previous_source) = &self.previous {
+            return previous_source.get_source();
+        }
+
+        self
+    }
+
+    pub fn from(node: &DataFlowNode, taint_policy: &TaintPolicy) -> Self {
         match &node.kind {
             DataFlowNodeKind::Vertex {
                 pos,
@@ -89,7 +118,7 @@ impl TaintedNode {
                 let mut sinks = vec![];
 
                 for source_type in types {
-                    sinks.extend(taint::get_sinks_for_sources(source_type));
+                    sinks.extend(taint_policy.get_sinks_for_source(source_type));
                 }
 
                 TaintedNode {
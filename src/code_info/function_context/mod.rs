@@ -75,4 +75,30 @@ impl FunctionContext {
             }
         }
     }
+    // uses of deprecated symbols are allowed inside deprecated functions and classes
+    pub fn is_deprecated(&self, codebase: &CodebaseInfo) -> bool {
+        let functionlike_storage = match self.calling_functionlike_id {
+            Some(FunctionLikeIdentifier::Function(function_id)) => codebase
+                .functionlike_infos
+                .get(&(function_id, StrId::EMPTY)),
+            Some(FunctionLikeIdentifier::Method(classlike_name, method_name)) => codebase
+                .functionlike_infos
+                .get(&(classlike_name, method_name)),
+            _ => None,
+        };
+
+        if let Some(functionlike_storage) = functionlike_storage {
+            if functionlike_storage.deprecated.is_some() {
+                return true;
+            }
+        }
+
+        if let Some(calling_class) = self.calling_class {
+            if let Some(classlike_storage) = codebase.classlike_infos.get(&calling_class) {
+                return classlike_storage.deprecated.is_some();
+            }
+        }
+
+        false
+    }
 }
@@ -72,7 +72,8 @@ pub struct FunctionLikeInfo {
 
     pub suppressed_issues: Vec<(IssueKind, HPos)>,
 
-    pub deprecated: bool,
+    // the message given to __Deprecated, if the function is deprecated
+    pub deprecated: Option<String>,
 
     /**
      * An array holding the class template "as" types.
@@ -124,6 +125,14 @@ pub struct FunctionLikeInfo {
 
     pub removed_taints: Vec<SinkType>,
 
+    /**
+     * Storage channels that this function's arguments are written to, and that
+     * the return values of LoadsTaint functions for the same channel are read from
+     */
+    pub stored_taint_channels: Vec<String>,
+
+    pub loaded_taint_channels: Vec<String>,
+
     pub attributes: Vec<AttributeInfo>,
 
     pub method_info: Option<Box<MethodInfo>>,
@@ -168,7 +177,7 @@ impl FunctionLikeInfo {
             is_populated: false,
             user_defined: false,
             suppressed_issues: vec![],
-            deprecated: false,
+            deprecated: None,
             template_types: vec![],
             has_visitor_issues: false,
             has_yield: false,
@@ -178,6 +187,8 @@ impl FunctionLikeInfo {
             taint_source_types: vec![],
             added_taints: vec![],
             removed_taints: vec![],
+            stored_taint_channels: vec![],
+            loaded_taint_channels: vec![],
             attributes: Vec::new(),
             method_info: None,
             is_async: false,
@@ -4,20 +4,28 @@ use std::{hash::Hasher, str::FromStr};
 use hakana_str::StrId;
 use rustc_hash::FxHashSet;
 use serde::{Deserialize, Serialize};
-use strum_macros::{Display, EnumString};
+use strum_macros::{Display, EnumString, EnumVariantNames};
 
 use crate::{
     code_location::{HPos, StmtStart},
+    data_flow::path::PathKind,
     function_context::FunctionLikeIdentifier,
     taint::SinkType,
 };
 
-#[derive(Clone, PartialEq, Eq, Hash, Display, Debug, Serialize, Deserialize, EnumString)]
+#[derive(
+    Clone, PartialEq, Eq, Hash, Display, Debug, Serialize, Deserialize, EnumString, EnumVariantNames,
+)]
 pub enum IssueKind {
     AbstractInstantiation,
     ExtendFinalClass,
     CannotInferGenericParam,
     CustomIssue(Box<String>),
+    DeprecatedClass,
+    DeprecatedConstant,
+    DeprecatedFunction,
+    DeprecatedMethod,
+    DeprecatedProperty,
     DuplicateEnumValue,
     EmptyBlock,
     FalsableReturnStatement,
@@ -44,6 +52,7 @@ pub enum IssueKind {
     InvalidReturnStatement,
     InvalidReturnType,
     InvalidReturnValue,
+    InvalidUpcast,
     LessSpecificArgument,
     LessSpecificNestedAnyArgumentType,
     LessSpecificNestedAnyReturnStatement,
@@ -71,6 +80,7 @@ pub enum IssueKind {
     MixedPropertyTypeCoercion,
     MixedReturnStatement,
     NoValue,
+    NonExhaustiveSwitch,
     NonExistentClass,
     NonExistentClassConstant,
     NonExistentClasslike,
@@ -111,6 +121,7 @@ pub enum IssueKind {
     RedundantTypeComparison,
     ShadowedLoopVar,
     StrictObjectEquality,
+    TaintAnalysisTruncated,
     TaintedData(Box<SinkType>),
     TestOnlyCall,
     UndefinedIntArrayOffset,
@@ -228,15 +239,41 @@ impl IssueKind {
     }
 }
 
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Hash, Display, Default, Serialize, Deserialize, EnumString,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum IssueSeverity {
+    #[default]
+    Error,
+    Warning,
+    Info,
+    Off,
+}
+
 #[derive(Clone, Debug, Eq, Serialize, Deserialize)]
 pub struct Issue {
     pub kind: IssueKind,
     pub description: String,
     pub pos: HPos,
+    pub severity: IssueSeverity,
     pub can_fix: bool,
     pub fixme_added: bool,
     pub symbol: (StrId, StrId),
     pub insertion_start: Option<StmtStart>,
+    pub taint_trace: Option<Vec<TaintTraceStep>>,
+    // the unique id of the source node a TaintedData path starts from
+    pub taint_source_id: Option<String>,
+}
+
+// a single hop in the path tainted data took from its source to a sink
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TaintTraceStep {
+    pub label: String,
+    pub pos: Option<HPos>,
+    // how the data got here from the previous step, if there was one
+    pub path_kind: Option<PathKind>,
 }
 
 impl PartialEq for Issue {
@@ -272,15 +309,33 @@ impl Issue {
                 }
             },
             pos,
+            severity: IssueSeverity::Error,
             can_fix: false,
             fixme_added: false,
             insertion_start: None,
+            taint_trace: None,
+            taint_source_id: None,
+        }
+    }
+
+    // identifies a TaintedData issue across runs by where its data came from and where it ended up
+    pub fn get_taint_fingerprint(&self, path: &str) -> Option<String> {
+        if let (IssueKind::TaintedData(sink_type), Some(taint_source_id)) =
+            (&self.kind, &self.taint_source_id)
+        {
+            Some(format!(
+                "{}|{:?}|{}:{}:{}",
+                taint_source_id, sink_type, path, self.pos.start_line, self.pos.start_column
+            ))
+        } else {
+            None
         }
     }
 
     pub fn format(&self, path: &String) -> String {
         format!(
-            "ERROR: {} - {}:{}:{} - {}\n",
+            "{}: {} - {}:{}:{} - {}\n",
+            self.severity.to_string().to_uppercase(),
             self.kind.to_string(),
             path,
             self.pos.start_line,
@@ -288,6 +343,36 @@ impl Issue {
             self.description
         )
     }
+
+    // the comment that suppresses this issue, and the offset it should be inserted at
+    pub fn get_fixme_insertion(&self) -> Option<(u32, String)> {
+        let insertion_start = self.insertion_start.as_ref()?;
+
+        Some((
+            insertion_start.offset,
+            format!(
+                "/* HAKANA_FIXME[{}]{} */{}",
+                self.kind.to_string(),
+                if let IssueKind::UnusedParameter
+                | IssueKind::UnusedAssignment
+                | IssueKind::UnusedAssignmentInClosure
+                | IssueKind::UnusedAssignmentStatement
+                | IssueKind::UnusedStatement
+                | IssueKind::UnusedFunction
+                | IssueKind::UnusedPrivateMethod = self.kind
+                {
+                    "".to_string()
+                } else {
+                    " ".to_string() + &self.description
+                },
+                if insertion_start.add_newline {
+                    "\n".to_string() + &"\t".repeat(insertion_start.column as usize)
+                } else {
+                    " ".to_string()
+                }
+            ),
+        ))
+    }
 }
 
 pub fn get_issue_from_comment(
@@ -37,5 +37,7 @@ pub struct PropertyInfo {
 
     pub is_internal: bool,
 
+    pub deprecated: Option<String>,
+
     pub suppressed_issues: Option<FxHashMap<IssueKind, HPos>>,
 }
@@ -1,8 +1,10 @@
 use serde::{Deserialize, Serialize};
 use std::{hash::Hash, str::FromStr};
-use strum_macros::{Display, EnumString};
+use strum_macros::{Display, EnumString, EnumVariantNames};
 
-#[derive(Clone, PartialEq, Eq, Hash, Display, Debug, Serialize, Deserialize, EnumString)]
+#[derive(
+    Clone, PartialEq, Eq, Hash, Display, Debug, Serialize, Deserialize, EnumString, EnumVariantNames,
+)]
 pub enum SourceType {
     UriRequestHeader,
     NonUriRequestHeader,
@@ -10,23 +12,36 @@ pub enum SourceType {
     UserPII,
     UserPassword,
     SystemSecret,
+    // org-specific kinds of data, written as Custom:Name in attributes and config
+    Custom(String),
 }
 
 impl SourceType {
-    pub fn get_error_message(&self) -> &str {
+    pub fn get_error_message(&self) -> String {
         match self {
-            SourceType::UriRequestHeader => "a URL query string",
-            SourceType::NonUriRequestHeader => "a server request",
-            SourceType::RawUserData => "raw user-controllable data",
-            SourceType::UserPassword => "a user secret",
-            SourceType::UserPII => "PII user data",
-            SourceType::SystemSecret => "a system secret",
+            SourceType::UriRequestHeader => "a URL query string".to_string(),
+            SourceType::NonUriRequestHeader => "a server request".to_string(),
+            SourceType::RawUserData => "raw user-controllable data".to_string(),
+            SourceType::UserPassword => "a user secret".to_string(),
+            SourceType::UserPII => "PII user data".to_string(),
+            SourceType::SystemSecret => "a system secret".to_string(),
+            SourceType::Custom(str) => format!("custom source {}", str),
         }
     }
 }
 
 #[derive(
-    Clone, PartialEq, Eq, Hash, Display, Debug, Serialize, Deserialize, EnumString, Default,
+    Clone,
+    PartialEq,
+    Eq,
+    Hash,
+    Display,
+    Debug,
+    Serialize,
+    Deserialize,
+    EnumString,
+    EnumVariantNames,
+    Default,
 )]
 pub enum SinkType {
     #[default]
@@ -89,12 +104,52 @@ const PAIRS: [(SourceType, SinkType); 31] = [
     (SourceType::SystemSecret, SinkType::Output),
 ];
 
-pub fn get_sinks_for_sources(source: &SourceType) -> Vec<SinkType> {
-    PAIRS
-        .into_iter()
-        .filter(|p| &p.0 == source)
-        .map(|p| p.1)
-        .collect()
+// The flows from sources to sinks that are reported. Defaults to PAIRS, but projects
+// can forbid or allow other flows in their config.
+#[derive(Clone, Debug)]
+pub struct TaintPolicy {
+    forbidden_flows: Vec<(SourceType, SinkType)>,
+}
+
+impl Default for TaintPolicy {
+    fn default() -> Self {
+        Self {
+            forbidden_flows: PAIRS.to_vec(),
+        }
+    }
+}
+
+impl TaintPolicy {
+    pub fn empty() -> Self {
+        Self {
+            forbidden_flows: vec![],
+        }
+    }
+
+    pub fn get_sinks_for_source(&self, source: &SourceType) -> Vec<SinkType> {
+        self.forbidden_flows
+            .iter()
+            .filter(|p| &p.0 == source)
+            .map(|p| p.1.clone())
+            .collect()
+    }
+
+    pub fn forbids(&self, source: &SourceType, sink: &SinkType) -> bool {
+        self.forbidden_flows
+            .iter()
+            .any(|p| &p.0 == source && &p.1 == sink)
+    }
+
+    pub fn forbid(&mut self, source: SourceType, sink: SinkType) {
+        if !self.forbids(&source, &sink) {
+            self.forbidden_flows.push((source, sink));
+        }
+    }
+
+    pub fn allow(&mut self, source: &SourceType, sink: &SinkType) {
+        self.forbidden_flows
+            .retain(|p| &p.0 != source || &p.1 != sink);
+    }
 }
 
 impl SinkType {
@@ -137,7 +192,13 @@ impl SinkType {
 }
 
 pub fn string_to_source_types(str: String) -> Option<SourceType> {
-    SourceType::from_str(&str).ok()
+    if let Some(custom_source) = str.strip_prefix("Custom:") {
+        Some(SourceType::Custom(custom_source.to_string()))
+    } else if str == "Custom" {
+        None
+    } else {
+        SourceType::from_str(&str).ok()
+    }
 }
 
 pub fn string_to_sink_types(str: String) -> Vec<SinkType> {
@@ -27,7 +27,7 @@ use oxidized::{
 
 use crate::{
     functionlike_scanner::{self, adjust_location_from_comments},
-    get_function_hashes, simple_type_inferer,
+    get_deprecation_message, get_function_hashes, simple_type_inferer,
 };
 use crate::{get_uses_hash, typehint_resolver::get_type_from_hint};
 
@@ -574,6 +574,8 @@ pub(crate) fn scan(
 
     storage.specialize_instance = true;
 
+    storage.deprecated = get_deprecation_message(&classlike_node.user_attributes.0, resolved_names);
+
     for user_attribute in &classlike_node.user_attributes {
         let name = *resolved_names
             .get(&(user_attribute.name.0.start_offset() as u32))
@@ -745,7 +747,11 @@ fn handle_reqs(
                     storage.all_class_interfaces.push(require_name);
                     storage.required_classlikes.push(require_name);
                 }
-                aast::RequireKind::RequireClass => todo!(),
+                aast::RequireKind::RequireClass => {
+                    // the trait can only be used by this class, which is not a parent
+                    storage.required_classlikes.push(require_name);
+                    continue;
+                }
             };
 
             storage.template_extended_offsets.insert(
@@ -876,6 +882,7 @@ fn visit_xhp_attribute(
         soft_readonly: false,
         is_promoted: false,
         is_internal: false,
+        deprecated: None,
         suppressed_issues: None,
     };
 
@@ -955,6 +962,7 @@ fn visit_class_const_declaration(
         },
         unresolved_value: None,
         is_abstract: matches!(const_node.kind, ClassConstKind::CCAbstract(..)),
+        deprecated: get_deprecation_message(&const_node.user_attributes.0, resolved_names),
     };
 
     classlike_storage.constants.insert(name, const_storage);
@@ -1128,6 +1136,7 @@ fn visit_property_declaration(
         soft_readonly: false,
         is_promoted: false,
         is_internal: matches!(property_node.visibility, ast_defs::Visibility::Internal),
+        deprecated: get_deprecation_message(&property_node.user_attributes.0, resolved_names),
         suppressed_issues: None,
     };
 
@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use crate::get_deprecation_message;
 use crate::simple_type_inferer;
 use crate::typehint_resolver::get_type_from_hint;
 use crate::typehint_resolver::get_type_from_optional_hint;
@@ -329,6 +330,8 @@ pub(crate) fn get_functionlike(
             (name_start_offset - name_line_start_offset) as u16 - name_offset as u16;
     }
 
+    functionlike_info.deprecated = get_deprecation_message(user_attributes, resolved_names);
+
     for user_attribute in user_attributes {
         let attribute_name = *resolved_names
             .get(&(user_attribute.name.0.start_offset() as u32))
@@ -360,6 +363,27 @@ pub(crate) fn get_functionlike(
             StrId::HAKANA_SECURITY_ANALYSIS_SPECIALIZE_CALL => {
                 functionlike_info.specialize_call = true;
             }
+            StrId::HAKANA_SECURITY_ANALYSIS_STORES_TAINT
+            | StrId::HAKANA_SECURITY_ANALYSIS_LOADS_TAINT => {
+                let mut channels = vec![];
+
+                for attribute_param_expr in &user_attribute.params {
+                    let attribute_param_type =
+                        simple_type_inferer::infer(attribute_param_expr, resolved_names);
+
+                    if let Some(attribute_param_type) = attribute_param_type {
+                        if let Some(str) = attribute_param_type.get_single_literal_string_value() {
+                            channels.push(str);
+                        }
+                    }
+                }
+
+                if attribute_name == StrId::HAKANA_SECURITY_ANALYSIS_STORES_TAINT {
+                    functionlike_info.stored_taint_channels = channels;
+                } else {
+                    functionlike_info.loaded_taint_channels = channels;
+                }
+            }
             StrId::HAKANA_SECURITY_ANALYSIS_IGNORE_PATH => {
                 functionlike_info.ignore_taint_path = true;
             }
@@ -183,6 +183,7 @@ impl<'ast> Visitor<'ast> for Scanner<'_> {
                 inferred_type: simple_type_inferer::infer(&gc.value, self.resolved_names),
                 unresolved_value: None,
                 is_abstract: false,
+                deprecated: None,
             },
         );
 
@@ -567,8 +568,14 @@ impl<'ast> Visitor<'ast> for Scanner<'_> {
     fn visit_stmt_(&mut self, c: &mut Context, p: &aast::Stmt_<(), ()>) -> Result<(), ()> {
         let result = p.recurse(c, self);
 
-        if let aast::Stmt_::Throw(..) = &p {
-            c.has_throw = true;
+        match &p {
+            aast::Stmt_::Throw(..) => {
+                c.has_throw = true;
+            }
+            aast::Stmt_::YieldBreak => {
+                c.has_yield = true;
+            }
+            _ => {}
         }
 
         result
@@ -814,6 +821,32 @@ fn get_uses_hash(uses: &Vec<(StrId, StrId)>) -> u64 {
     hasher.finish()
 }
 
+// returns the message given to __Deprecated (or an empty string) if the attribute is present
+fn get_deprecation_message(
+    user_attributes: &[aast::UserAttribute<(), ()>],
+    resolved_names: &FxHashMap<u32, StrId>,
+) -> Option<String> {
+    for user_attribute in user_attributes {
+        if resolved_names.get(&(user_attribute.name.0.start_offset() as u32))
+            == Some(&StrId::DEPRECATED)
+        {
+            if let Some(attribute_param_expr) = user_attribute.params.first() {
+                if let Some(attribute_param_type) =
+                    simple_type_inferer::infer(attribute_param_expr, resolved_names)
+                {
+                    if let Some(str) = attribute_param_type.get_single_literal_string_value() {
+                        return Some(str);
+                    }
+                }
+            }
+
+            return Some("".to_string());
+        }
+    }
+
+    None
+}
+
 fn get_function_hashes(
     file_contents: &str,
     def_location: &HPos,
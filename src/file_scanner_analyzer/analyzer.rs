@@ -94,6 +94,11 @@ pub fn analyze_files(
                     &mut new_analysis_result,
                     resolved_names,
                     &logger,
+                    scan_data
+                        .file_system
+                        .unsaved_file_contents
+                        .get(&file_path)
+                        .cloned(),
                 );
             }
 
@@ -152,6 +157,11 @@ pub fn analyze_files(
                             &mut new_analysis_result,
                             resolved_names,
                             &logger,
+                            scan_data
+                                .file_system
+                                .unsaved_file_contents
+                                .get(&file_path)
+                                .cloned(),
                         );
                     }
 
@@ -186,7 +196,7 @@ pub fn analyze_files(
     Ok(())
 }
 
-fn analyze_file(
+pub(crate) fn analyze_file(
     file_path: FilePath,
     str_path: &String,
     last_updated_time: Option<u64>,
@@ -196,6 +206,7 @@ fn analyze_file(
     analysis_result: &mut AnalysisResult,
     resolved_names: &FxHashMap<u32, StrId>,
     logger: &Logger,
+    unsaved_file_contents: Option<String>,
 ) -> Duration {
     logger.log_debug_sync(&format!("Analyzing {}", &str_path));
 
@@ -233,7 +244,7 @@ fn analyze_file(
         }
     }
 
-    let aast = match get_aast_for_path(file_path, str_path) {
+    let aast = match get_aast_for_path(file_path, str_path, unsaved_file_contents) {
         Ok(aast) => (aast.0, aast.1),
         Err(err) => {
             analysis_result.has_invalid_hack_files = true;
@@ -1,6 +1,7 @@
 use hakana_logger::Logger;
 use hakana_reflection_info::code_location::FilePath;
 use hakana_reflection_info::codebase_info::CodebaseInfo;
+use hakana_reflection_info::data_flow::graph::DataFlowGraph;
 use hakana_reflection_info::issue::Issue;
 use hakana_reflection_info::symbol_references::SymbolReferences;
 use hakana_str::Interner;
@@ -98,6 +99,31 @@ pub(crate) fn load_cached_existing_issues(
     None
 }
 
+pub(crate) fn load_cached_existing_dataflow_graphs(
+    existing_dataflow_graphs_path: &String,
+    use_codebase_cache: bool,
+    config_hash: u64,
+    logger: &Logger,
+) -> Option<FxHashMap<(StrId, StrId), DataFlowGraph>> {
+    if Path::new(existing_dataflow_graphs_path).exists() && use_codebase_cache {
+        logger.log_sync("Deserializing existing dataflow graphs cache");
+        let serialized = fs::read(existing_dataflow_graphs_path)
+            .unwrap_or_else(|_| panic!("Could not read file {}", &existing_dataflow_graphs_path));
+        if let Ok((cached_config_hash, d)) =
+            bincode::deserialize::<(u64, FxHashMap<(StrId, StrId), DataFlowGraph>)>(&serialized)
+        {
+            // graphs built with a different taint spec or sources are stale
+            if cached_config_hash == config_hash {
+                return Some(d);
+            }
+
+            logger.log_sync("Discarding dataflow graphs cache built with a different config");
+        }
+    }
+
+    None
+}
+
 pub(crate) fn get_file_manifest(cache_dir: &String) -> Option<VirtualFileSystem> {
     let aast_manifest_path = format!("{}/manifest", cache_dir);
 
@@ -1,15 +1,19 @@
 use hakana_logger::Logger;
 use hakana_reflection_info::analysis_result::AnalysisResult;
-use hakana_reflection_info::code_location::FilePath;
+use hakana_reflection_info::code_location::{FilePath, HPos};
 use hakana_reflection_info::codebase_info::CodebaseInfo;
+use hakana_reflection_info::data_flow::graph::DataFlowGraph;
+use hakana_reflection_info::data_flow::node::DataFlowNodeKind;
 use hakana_reflection_info::diff::CodebaseDiff;
-use hakana_reflection_info::issue::Issue;
+use hakana_reflection_info::issue::{Issue, IssueKind};
 use hakana_reflection_info::symbol_references::SymbolReferences;
+use hakana_reflection_info::t_union::TUnion;
 use hakana_str::Interner;
 use hakana_str::StrId;
 use rustc_hash::FxHashMap;
 use rustc_hash::FxHashSet;
 
+use crate::cache::load_cached_existing_dataflow_graphs;
 use crate::cache::load_cached_existing_issues;
 use crate::cache::load_cached_existing_references;
 
@@ -19,6 +23,9 @@ pub(crate) struct CachedAnalysis {
     pub safe_symbol_members: FxHashSet<(StrId, StrId)>,
     pub existing_issues: FxHashMap<FilePath, Vec<Issue>>,
     pub symbol_references: SymbolReferences,
+    pub existing_expr_types: FxHashMap<FilePath, FxHashMap<(u32, u32), TUnion>>,
+    pub existing_symbol_uses: FxHashMap<FilePath, FxHashMap<(u32, u32), (StrId, StrId)>>,
+    pub existing_dataflow_graphs: FxHashMap<(StrId, StrId), DataFlowGraph>,
 }
 
 pub(crate) fn mark_safe_symbols_from_diff(
@@ -30,35 +37,77 @@ pub(crate) fn mark_safe_symbols_from_diff(
     files_to_analyze: &mut Vec<String>,
     issues_path: &Option<String>,
     references_path: &Option<String>,
+    dataflow_graphs_path: &Option<String>,
+    dataflow_graph_config_hash: u64,
     previous_analysis_result: Option<AnalysisResult>,
 ) -> CachedAnalysis {
-    let (existing_references, mut existing_issues) = if let Some(previous_analysis_result) =
-        previous_analysis_result
-    {
-        (
-            previous_analysis_result.symbol_references,
-            previous_analysis_result.emitted_issues,
-        )
-    } else if let (Some(issues_path), Some(references_path)) = (issues_path, references_path) {
-        let existing_references = if let Some(existing_references) =
-            load_cached_existing_references(references_path, true, logger)
-        {
-            existing_references
-        } else {
-            return CachedAnalysis::default();
-        };
+    let mut existing_expr_types = FxHashMap::default();
+    let mut existing_symbol_uses = FxHashMap::default();
+
+    let (existing_references, mut existing_issues, mut existing_dataflow_graphs) =
+        if let Some(previous_analysis_result) = previous_analysis_result {
+            existing_expr_types = previous_analysis_result.expr_types;
+            existing_symbol_uses = previous_analysis_result.symbol_uses;
+
+            let mut existing_issues = previous_analysis_result.emitted_issues;
+
+            // taint paths are found afresh in the whole program graph on every run
+            for file_issues in existing_issues.values_mut() {
+                file_issues.retain(|issue| {
+                    !matches!(
+                        issue.kind,
+                        IssueKind::TaintedData(_) | IssueKind::TaintAnalysisTruncated
+                    )
+                });
+            }
 
-        let existing_issues =
-            if let Some(existing_issues) = load_cached_existing_issues(issues_path, true, logger) {
+            (
+                previous_analysis_result.symbol_references,
+                existing_issues,
+                previous_analysis_result.symbol_dataflow_graphs,
+            )
+        } else if let (Some(issues_path), Some(references_path)) = (issues_path, references_path) {
+            let existing_references = if let Some(existing_references) =
+                load_cached_existing_references(references_path, true, logger)
+            {
+                existing_references
+            } else {
+                return CachedAnalysis::default();
+            };
+
+            let existing_issues = if let Some(existing_issues) =
+                load_cached_existing_issues(issues_path, true, logger)
+            {
                 existing_issues
             } else {
                 return CachedAnalysis::default();
             };
 
-        (existing_references, existing_issues)
-    } else {
-        return CachedAnalysis::default();
-    };
+            // only whole-program analysis has graphs to reuse
+            let existing_dataflow_graphs = if let Some(dataflow_graphs_path) = dataflow_graphs_path
+            {
+                if let Some(existing_dataflow_graphs) = load_cached_existing_dataflow_graphs(
+                    dataflow_graphs_path,
+                    true,
+                    dataflow_graph_config_hash,
+                    logger,
+                ) {
+                    existing_dataflow_graphs
+                } else {
+                    return CachedAnalysis::default();
+                }
+            } else {
+                FxHashMap::default()
+            };
+
+            (
+                existing_references,
+                existing_issues,
+                existing_dataflow_graphs,
+            )
+        } else {
+            return CachedAnalysis::default();
+        };
 
     let (invalid_symbols_and_members, partially_invalid_symbols) =
         if let Some(invalid_symbols) = existing_references.get_invalid_symbols(&codebase_diff) {
@@ -111,12 +160,33 @@ pub(crate) fn mark_safe_symbols_from_diff(
 
     files_to_analyze.retain(|full_path| invalid_files.contains(&full_path.as_str()));
 
+    // graphs are only reused for symbols that won't be analyzed again
+    existing_dataflow_graphs.retain(|symbol, _| {
+        if codebase.files.contains_key(&FilePath(symbol.0)) {
+            !invalid_files.contains(interner.lookup(&symbol.0))
+        } else {
+            cached_analysis.safe_symbols.contains(&symbol.0)
+                || cached_analysis.safe_symbol_members.contains(symbol)
+        }
+    });
+
+    existing_expr_types.retain(|file_path, _| codebase.files.contains_key(file_path));
+    existing_symbol_uses.retain(|file_path, _| codebase.files.contains_key(file_path));
+
+    update_offsets_from_diff(&mut existing_expr_types, &codebase_diff);
+    update_offsets_from_diff(&mut existing_symbol_uses, &codebase_diff);
+
+    update_dataflow_graphs_from_diff(&mut existing_dataflow_graphs, &codebase_diff);
+
     update_issues_from_diff(
         &mut existing_issues,
         codebase_diff,
         &invalid_symbols_and_members,
     );
     cached_analysis.existing_issues = existing_issues;
+    cached_analysis.existing_expr_types = existing_expr_types;
+    cached_analysis.existing_symbol_uses = existing_symbol_uses;
+    cached_analysis.existing_dataflow_graphs = existing_dataflow_graphs;
 
     cached_analysis
 }
@@ -162,19 +232,95 @@ fn update_issues_from_diff(
 
         if !diff_map.is_empty() {
             for issue in file_issues {
-                for (from, to, file_offset, line_offset) in &diff_map {
-                    if &issue.pos.start_offset >= from && &issue.pos.start_offset <= to {
-                        issue.pos.start_offset =
-                            ((issue.pos.start_offset as isize) + file_offset) as u32;
-                        issue.pos.end_offset =
-                            ((issue.pos.end_offset as isize) + file_offset) as u32;
-                        issue.pos.start_line =
-                            ((issue.pos.start_line as isize) + line_offset) as u32;
-                        issue.pos.end_line = ((issue.pos.end_line as isize) + line_offset) as u32;
-                        break;
-                    }
+                update_pos_from_diff(&mut issue.pos, &diff_map);
+            }
+        }
+    }
+}
+
+// the nodes of reused graphs can point at code that has since moved
+fn update_dataflow_graphs_from_diff(
+    existing_dataflow_graphs: &mut FxHashMap<(StrId, StrId), DataFlowGraph>,
+    codebase_diff: &CodebaseDiff,
+) {
+    if codebase_diff.diff_map.is_empty() {
+        return;
+    }
+
+    for graph in existing_dataflow_graphs.values_mut() {
+        for node in graph
+            .vertices
+            .values_mut()
+            .chain(graph.sources.values_mut())
+            .chain(graph.sinks.values_mut())
+        {
+            let pos = match &mut node.kind {
+                DataFlowNodeKind::Vertex { pos, .. }
+                | DataFlowNodeKind::TaintSource { pos, .. } => pos.as_mut(),
+                DataFlowNodeKind::TaintSink { pos, .. }
+                | DataFlowNodeKind::VariableUseSource { pos, .. }
+                | DataFlowNodeKind::VariableUseSink { pos }
+                | DataFlowNodeKind::DataSource { pos, .. } => Some(pos),
+                DataFlowNodeKind::ForLoopInit { .. } => None,
+            };
+
+            if let Some(pos) = pos {
+                if let Some(diff_map) = codebase_diff.diff_map.get(&pos.file_path) {
+                    update_pos_from_diff(pos, diff_map);
                 }
             }
         }
     }
 }
+
+fn update_pos_from_diff(pos: &mut HPos, diff_map: &[(u32, u32, isize, isize)]) {
+    for (from, to, file_offset, line_offset) in diff_map {
+        if &pos.start_offset >= from && &pos.start_offset <= to {
+            pos.start_offset = ((pos.start_offset as isize) + file_offset) as u32;
+            pos.end_offset = ((pos.end_offset as isize) + file_offset) as u32;
+            pos.start_line = ((pos.start_line as isize) + line_offset) as u32;
+            pos.end_line = ((pos.end_line as isize) + line_offset) as u32;
+            break;
+        }
+    }
+}
+
+fn update_offsets_from_diff<T>(
+    existing_entries: &mut FxHashMap<FilePath, FxHashMap<(u32, u32), T>>,
+    codebase_diff: &CodebaseDiff,
+) {
+    for (existing_file, file_entries) in existing_entries.iter_mut() {
+        if let Some(deletion_ranges) = codebase_diff.deletion_ranges_map.get(existing_file) {
+            file_entries.retain(|(start_offset, _), _| {
+                for (from, to) in deletion_ranges {
+                    if start_offset >= from && start_offset <= to {
+                        return false;
+                    }
+                }
+
+                true
+            });
+        }
+
+        if let Some(diff_map) = codebase_diff.diff_map.get(existing_file) {
+            *file_entries = file_entries
+                .drain()
+                .map(|((start_offset, end_offset), entry)| {
+                    for (from, to, file_offset, _) in diff_map {
+                        if &start_offset >= from && &start_offset <= to {
+                            return (
+                                (
+                                    ((start_offset as isize) + file_offset) as u32,
+                                    ((end_offset as isize) + file_offset) as u32,
+                                ),
+                                entry,
+                            );
+                        }
+                    }
+
+                    ((start_offset, end_offset), entry)
+                })
+                .collect();
+        }
+    }
+}
@@ -14,11 +14,15 @@ pub enum FileStatus {
     Deleted,
     DeletedDir,
     Modified(u64, u64),
+    // the contents of an editor buffer that has not been saved to disk
+    Unsaved(String),
 }
 
 #[derive(Clone, Default, Serialize, Deserialize, Debug)]
 pub struct VirtualFileSystem {
     pub file_hashes_and_times: FxHashMap<FilePath, (u64, u64)>,
+    #[serde(skip)]
+    pub unsaved_file_contents: FxHashMap<FilePath, String>,
 }
 
 impl VirtualFileSystem {
@@ -75,6 +79,7 @@ impl VirtualFileSystem {
 
         for deleted_file in deleted_files {
             self.file_hashes_and_times.remove(&deleted_file);
+            self.unsaved_file_contents.remove(&deleted_file);
         }
 
         for (file_path, status) in language_server_changes {
@@ -82,7 +87,22 @@ impl VirtualFileSystem {
 
             match status {
                 FileStatus::Unchanged(_, _) => panic!(),
+                FileStatus::Unsaved(file_contents) => {
+                    self.add_unsaved_path(
+                        path,
+                        file_contents,
+                        interner,
+                        files_to_scan,
+                        config,
+                        files_to_analyze,
+                    );
+                }
                 FileStatus::Added(_, _) | FileStatus::Modified(_, _) => {
+                    if let Some(interned_file_path) = interner.get(&file_path) {
+                        self.unsaved_file_contents
+                            .remove(&FilePath(interned_file_path));
+                    }
+
                     self.add_path(
                         path,
                         &vec![],
@@ -101,6 +121,56 @@ impl VirtualFileSystem {
         }
     }
 
+    fn add_unsaved_path(
+        &mut self,
+        path: &std::path::Path,
+        file_contents: String,
+        interner: &mut Interner,
+        files_to_scan: &mut Vec<String>,
+        config: &Config,
+        files_to_analyze: &mut Vec<String>,
+    ) {
+        let str_path = path.to_str().unwrap().to_string();
+
+        let interned_file_path = FilePath(interner.intern(str_path.clone()));
+
+        // we keep the on-disk modification time so that the file isn't
+        // treated as having changed during analysis
+        let updated_time = if let Ok(metadata) = fs::metadata(path) {
+            metadata
+                .modified()
+                .unwrap()
+                .duration_since(SystemTime::UNIX_EPOCH)
+                .unwrap()
+                .as_micros() as u64
+        } else {
+            0
+        };
+
+        self.file_hashes_and_times.insert(
+            interned_file_path,
+            (
+                xxhash_rust::xxh3::xxh3_64(file_contents.as_bytes()),
+                updated_time,
+            ),
+        );
+
+        self.unsaved_file_contents
+            .insert(interned_file_path, file_contents);
+
+        files_to_scan.push(str_path.clone());
+
+        if !str_path.ends_with(".hhi") {
+            if matches!(config.graph_kind, GraphKind::WholeProgram(_)) {
+                if config.allow_taints_in_file(&str_path) {
+                    files_to_analyze.push(str_path);
+                }
+            } else {
+                files_to_analyze.push(str_path);
+            }
+        }
+    }
+
     pub(crate) fn get_file_statuses(
         &self,
         target_files: &Vec<String>,
@@ -5,15 +5,18 @@ use diff::{mark_safe_symbols_from_diff, CachedAnalysis};
 use file::{FileStatus, VirtualFileSystem};
 use hakana_aast_helper::get_aast_for_path_and_contents;
 use hakana_analyzer::config::Config;
-use hakana_analyzer::dataflow::program_analyzer::{find_connections, find_tainted_data};
+use hakana_analyzer::dataflow::program_analyzer::{
+    add_query_sources, add_taint_storage_paths, find_connections, find_tainted_data,
+};
 use hakana_logger::Logger;
 use hakana_reflection_info::analysis_result::AnalysisResult;
 use hakana_reflection_info::code_location::{FilePath, HPos};
 use hakana_reflection_info::codebase_info::CodebaseInfo;
-use hakana_reflection_info::data_flow::graph::{GraphKind, WholeProgramKind};
+use hakana_reflection_info::data_flow::graph::{DataFlowGraph, GraphKind, WholeProgramKind};
 use hakana_reflection_info::file_info::ParserError;
-use hakana_reflection_info::issue::{Issue, IssueKind};
+use hakana_reflection_info::issue::{Issue, IssueKind, IssueSeverity};
 use hakana_reflection_info::symbol_references::SymbolReferences;
+use hakana_reflection_info::t_union::TUnion;
 use hakana_str::{Interner, StrId};
 use indicatif::ProgressBar;
 use oxidized::aast;
@@ -124,6 +127,8 @@ pub async fn scan_and_analyze_async(
             &mut files_to_analyze,
             &None,
             &None,
+            &None,
+            config.get_dataflow_graph_config_hash(),
             previous_analysis_result,
         )
     } else {
@@ -152,6 +157,9 @@ pub async fn scan_and_analyze_async(
         resolved_names,
         cached_analysis.symbol_references,
         cached_analysis.existing_issues,
+        cached_analysis.existing_expr_types,
+        cached_analysis.existing_symbol_uses,
+        cached_analysis.existing_dataflow_graphs,
     );
 
     lsp_client
@@ -191,9 +199,46 @@ pub async fn scan_and_analyze_async(
         );
     }
 
+    apply_issue_severities(&mut analysis_result, &config, &scan_data.interner);
+
     Ok((analysis_result, scan_data))
 }
 
+// re-analyzes a single already-scanned file, e.g. with a config that has
+// issues_to_fix set so that its fixes can be offered without writing them
+pub fn analyze_single_file(
+    file_path: FilePath,
+    scan_data: &SuccessfulScanData,
+    config: &Arc<Config>,
+) -> AnalysisResult {
+    let mut analysis_result = AnalysisResult::new(config.graph_kind, SymbolReferences::new());
+
+    if let Some(resolved_names) = scan_data.resolved_names.get(&file_path) {
+        analyzer::analyze_file(
+            file_path,
+            &scan_data.interner.lookup(&file_path.0).to_string(),
+            scan_data
+                .file_system
+                .file_hashes_and_times
+                .get(&file_path)
+                .map(|k| k.1),
+            &scan_data.codebase,
+            &scan_data.interner,
+            config,
+            &mut analysis_result,
+            resolved_names,
+            &Logger::DevNull,
+            scan_data
+                .file_system
+                .unsaved_file_contents
+                .get(&file_path)
+                .cloned(),
+        );
+    }
+
+    analysis_result
+}
+
 pub fn scan_and_analyze(
     stubs_dirs: Vec<String>,
     filter: Option<String>,
@@ -264,6 +309,12 @@ pub fn scan_and_analyze(
             &mut files_to_analyze,
             &get_issues_path(cache_dir),
             &get_references_path(cache_dir),
+            &if let GraphKind::WholeProgram(_) = config.graph_kind {
+                get_dataflow_graphs_path(cache_dir)
+            } else {
+                None
+            },
+            config.get_dataflow_graph_config_hash(),
             previous_analysis_result,
         )
     } else {
@@ -299,6 +350,9 @@ pub fn scan_and_analyze(
         resolved_names,
         cached_analysis.symbol_references,
         cached_analysis.existing_issues,
+        cached_analysis.existing_expr_types,
+        cached_analysis.existing_symbol_uses,
+        cached_analysis.existing_dataflow_graphs,
     );
 
     logger.log_sync(&format!("Analyzing {} files", files_to_analyze.len()));
@@ -324,11 +378,15 @@ pub fn scan_and_analyze(
         ));
     }
 
-    let mut analysis_result = (*analysis_result.lock().unwrap()).clone();
+    // every analysis thread has finished, so this is the only reference left
+    let mut analysis_result = Arc::try_unwrap(analysis_result)
+        .unwrap()
+        .into_inner()
+        .unwrap();
 
     analysis_result.time_in_analysis = pure_file_analysis_time;
 
-    cache_analysis_data(cache_dir, &analysis_result)?;
+    cache_analysis_data(cache_dir, &analysis_result, &config)?;
 
     let scan_data = Arc::try_unwrap(arc_scan_data).unwrap();
 
@@ -345,19 +403,38 @@ pub fn scan_and_analyze(
     }
 
     if let GraphKind::WholeProgram(whole_program_kind) = config.graph_kind {
-        let issues = match whole_program_kind {
+        // the symbol graphs are already in the cache by now, so they only need to stay in
+        // memory for a diff-based run that has no cache to read them from
+        analysis_result.build_program_dataflow_graph(config.ast_diff && cache_dir.is_none());
+
+        add_taint_storage_paths(
+            &mut analysis_result.program_dataflow_graph,
+            &scan_data.codebase,
+        );
+
+        let (issues, truncated_taint_sources) = match whole_program_kind {
             WholeProgramKind::Taint => find_tainted_data(
                 &analysis_result.program_dataflow_graph,
                 &config,
                 &logger,
                 &scan_data.interner,
             ),
-            WholeProgramKind::Query => find_connections(
-                &analysis_result.program_dataflow_graph,
-                &config,
-                &logger,
-                &scan_data.interner,
-            ),
+            WholeProgramKind::Query => {
+                add_query_sources(
+                    &mut analysis_result.program_dataflow_graph,
+                    &config,
+                    &scan_data.codebase,
+                    &logger,
+                    &scan_data.interner,
+                );
+
+                find_connections(
+                    &analysis_result.program_dataflow_graph,
+                    &config,
+                    &logger,
+                    &scan_data.interner,
+                )
+            }
         };
 
         for issue in issues {
@@ -367,11 +444,38 @@ pub fn scan_and_analyze(
                 .or_default()
                 .push(issue);
         }
+
+        analysis_result.truncated_taint_sources = truncated_taint_sources;
     }
 
+    apply_issue_severities(&mut analysis_result, &config, &scan_data.interner);
+
     Ok((analysis_result, scan_data))
 }
 
+// severities are applied after caching so that changing them in config takes
+// effect without having to re-analyze
+fn apply_issue_severities(
+    analysis_result: &mut AnalysisResult,
+    config: &Config,
+    interner: &Interner,
+) {
+    for issues in [
+        &mut analysis_result.emitted_issues,
+        &mut analysis_result.emitted_definition_issues,
+    ] {
+        for (file_path, file_issues) in issues.iter_mut() {
+            let file_path_str = interner.lookup(&file_path.0);
+
+            for issue in file_issues.iter_mut() {
+                issue.severity = config.get_issue_severity(&issue.kind, file_path_str);
+            }
+
+            file_issues.retain(|issue| issue.severity != IssueSeverity::Off);
+        }
+    }
+}
+
 fn get_analysis_ready(
     config: &Arc<Config>,
     codebase: CodebaseInfo,
@@ -380,10 +484,16 @@ fn get_analysis_ready(
     resolved_names: FxHashMap<FilePath, FxHashMap<u32, StrId>>,
     symbol_references: SymbolReferences,
     existing_issues: FxHashMap<FilePath, Vec<Issue>>,
+    existing_expr_types: FxHashMap<FilePath, FxHashMap<(u32, u32), TUnion>>,
+    existing_symbol_uses: FxHashMap<FilePath, FxHashMap<(u32, u32), (StrId, StrId)>>,
+    existing_dataflow_graphs: FxHashMap<(StrId, StrId), DataFlowGraph>,
 ) -> (Arc<Mutex<AnalysisResult>>, Arc<SuccessfulScanData>) {
     let mut analysis_result = AnalysisResult::new(config.graph_kind, symbol_references);
 
     analysis_result.emitted_issues = existing_issues;
+    analysis_result.expr_types = existing_expr_types;
+    analysis_result.symbol_uses = existing_symbol_uses;
+    analysis_result.symbol_dataflow_graphs = existing_dataflow_graphs;
 
     let analysis_result = Arc::new(Mutex::new(analysis_result));
 
@@ -401,6 +511,7 @@ fn get_analysis_ready(
 fn cache_analysis_data(
     cache_dir: Option<&String>,
     analysis_result: &AnalysisResult,
+    config: &Config,
 ) -> Result<(), io::Error> {
     if let Some(references_path) = get_references_path(cache_dir) {
         let mut symbols_file = fs::File::create(references_path).unwrap();
@@ -413,6 +524,17 @@ fn cache_analysis_data(
         let serialized_issues = bincode::serialize(&analysis_result.emitted_issues).unwrap();
         issues_file.write_all(&serialized_issues)?;
     };
+    if let GraphKind::WholeProgram(_) = analysis_result.program_dataflow_graph.kind {
+        if let Some(dataflow_graphs_path) = get_dataflow_graphs_path(cache_dir) {
+            let mut dataflow_graphs_file = fs::File::create(dataflow_graphs_path).unwrap();
+            let serialized_dataflow_graphs = bincode::serialize(&(
+                config.get_dataflow_graph_config_hash(),
+                &analysis_result.symbol_dataflow_graphs,
+            ))
+            .unwrap();
+            dataflow_graphs_file.write_all(&serialized_dataflow_graphs)?;
+        }
+    }
     Ok(())
 }
 
@@ -424,11 +546,18 @@ fn get_references_path(cache_dir: Option<&String>) -> Option<String> {
     cache_dir.map(|cache_dir| format!("{}/references", cache_dir))
 }
 
+fn get_dataflow_graphs_path(cache_dir: Option<&String>) -> Option<String> {
+    cache_dir.map(|cache_dir| format!("{}/dataflow_graphs", cache_dir))
+}
+
 pub fn get_aast_for_path(
     file_path: FilePath,
     file_path_str: &str,
+    unsaved_file_contents: Option<String>,
 ) -> Result<(aast::Program<(), ()>, ScouredComments, String), ParserError> {
-    let file_contents = if file_path_str.starts_with("hsl_embedded_") {
+    let file_contents = if let Some(unsaved_file_contents) = unsaved_file_contents {
+        unsaved_file_contents
+    } else if file_path_str.starts_with("hsl_embedded_") {
         std::str::from_utf8(
             &HslAsset::get(file_path_str)
                 .unwrap_or_else(|| panic!("Could not read HSL file {}", file_path_str))
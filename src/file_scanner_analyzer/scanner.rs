@@ -323,6 +323,7 @@ pub fn scan_files(
                     analyze_map.contains(&str_path),
                     !test_patterns.iter().any(|p| p.matches(&str_path)),
                     &logger,
+                    file_system.unsaved_file_contents.get(*file_path).cloned(),
                 ) {
                     Ok(scanner_result) => {
                         resolved_names
@@ -377,6 +378,7 @@ pub fn scan_files(
                 let test_patterns = test_patterns.clone();
                 let logger = logger.clone();
                 let invalid_files = invalid_files.clone();
+                let unsaved_file_contents = file_system.unsaved_file_contents.clone();
 
                 let handle = std::thread::spawn(move || {
                     let mut new_codebase = CodebaseInfo::new();
@@ -402,6 +404,7 @@ pub fn scan_files(
                             analyze_map.contains(&str_path),
                             !test_patterns.iter().any(|p| p.matches(&str_path)),
                             &logger.clone(),
+                            unsaved_file_contents.get(file_path).cloned(),
                         ) {
                             local_resolved_names.insert(*file_path, scanner_result);
                         } else {
@@ -570,10 +573,11 @@ pub(crate) fn scan_file(
     user_defined: bool,
     is_production_code: bool,
     logger: &Logger,
+    unsaved_file_contents: Option<String>,
 ) -> Result<FxHashMap<u32, StrId>, ParserError> {
     logger.log_debug_sync(&format!("scanning {}", str_path));
 
-    let aast = get_aast_for_path(file_path, str_path);
+    let aast = get_aast_for_path(file_path, str_path, unsaved_file_contents);
 
     let aast = match aast {
         Ok(aast) => aast,
@@ -135,7 +135,7 @@ pub(crate) fn find_unused_definitions(
                         );
                 }
 
-                if config.can_add_issue(&issue) {
+                if config.can_add_issue(&issue, file_path) {
                     *analysis_result
                         .issue_counts
                         .entry(issue.kind.clone())
@@ -160,7 +160,7 @@ pub(crate) fn find_unused_definitions(
                     &Some(FunctionLikeIdentifier::Function(functionlike_name.0)),
                 );
 
-                if config.can_add_issue(&issue) {
+                if config.can_add_issue(&issue, file_path) {
                     *analysis_result
                         .issue_counts
                         .entry(issue.kind.clone())
@@ -226,7 +226,7 @@ pub(crate) fn find_unused_definitions(
                         );
                 }
 
-                if config.can_add_issue(&issue) {
+                if config.can_add_issue(&issue, file_path) {
                     *analysis_result
                         .issue_counts
                         .entry(issue.kind.clone())
@@ -258,7 +258,7 @@ pub(crate) fn find_unused_definitions(
                             &Some(FunctionLikeIdentifier::Function(*classlike_name)),
                         );
 
-                        if config.can_add_issue(&issue) {
+                        if config.can_add_issue(&issue, file_path) {
                             *analysis_result
                                 .issue_counts
                                 .entry(issue.kind.clone())
@@ -394,7 +394,7 @@ pub(crate) fn find_unused_definitions(
                                             - meta_start.start_column as u32,
                                     ),
                                 );
-                        } else if config.can_add_issue(&issue) {
+                        } else if config.can_add_issue(&issue, file_path) {
                             *analysis_result
                                 .issue_counts
                                 .entry(issue.kind.clone())
@@ -434,7 +434,7 @@ pub(crate) fn find_unused_definitions(
                                 &Some(FunctionLikeIdentifier::Method(*classlike_name, *method_name_ptr)),
                             );
 
-                        if config.can_add_issue(&issue) {
+                        if config.can_add_issue(&issue, file_path) {
                             *analysis_result
                                 .issue_counts
                                 .entry(issue.kind.clone())
@@ -525,7 +525,7 @@ pub(crate) fn find_unused_definitions(
                                         ),
                                     );
                             }
-                        } else if config.can_add_issue(&issue) {
+                        } else if config.can_add_issue(&issue, file_path) {
                             *analysis_result
                                 .issue_counts
                                 .entry(issue.kind.clone())
@@ -583,7 +583,7 @@ pub(crate) fn find_unused_definitions(
                         );
                 }
 
-                if config.can_add_issue(&issue) {
+                if config.can_add_issue(&issue, file_path) {
                     *analysis_result
                         .issue_counts
                         .entry(issue.kind.clone())
@@ -1,7 +1,7 @@
 use hakana_aast_helper::get_aast_for_path_and_contents;
 use hakana_aast_helper::name_context::NameContext;
 use hakana_analyzer::config::Config;
-use hakana_analyzer::dataflow::program_analyzer::find_tainted_data;
+use hakana_analyzer::dataflow::program_analyzer::{add_taint_storage_paths, find_tainted_data};
 use hakana_analyzer::file_analyzer;
 use hakana_logger::Logger;
 use hakana_reflection_info::analysis_result::AnalysisResult;
@@ -76,7 +76,11 @@ pub fn scan_and_analyze_single_file(
     )?;
 
     if matches!(analysis_config.graph_kind, GraphKind::WholeProgram(_)) {
-        let issues = find_tainted_data(
+        analysis_result.build_program_dataflow_graph(false);
+
+        add_taint_storage_paths(&mut analysis_result.program_dataflow_graph, codebase);
+
+        let (issues, _) = find_tainted_data(
             &analysis_result.program_dataflow_graph,
             &analysis_config,
             &Logger::DevNull,
@@ -125,6 +129,7 @@ pub fn get_single_file_codebase(
             false,
             false,
             &silent_logger,
+            None,
         )
         .unwrap();
     }
@@ -146,6 +151,7 @@ pub fn get_single_file_codebase(
             false,
             false,
             &silent_logger,
+            None,
         )
         .unwrap();
     }
@@ -166,6 +172,7 @@ pub fn get_single_file_codebase(
             false,
             false,
             &silent_logger,
+            None,
         )
         .unwrap();
     }
@@ -1,4 +1,6 @@
+use hakana_reflection_info::code_location::FilePath;
 use hakana_reflection_info::codebase_info::CodebaseInfo;
+use hakana_reflection_info::issue::TaintTraceStep;
 use hakana_str::Interner;
 use hakana_workhorse::wasm::{get_single_file_codebase, scan_and_analyze_single_file};
 use serde_json::json;
@@ -35,7 +37,7 @@ impl ScannerAndAnalyzer {
                 {
                     for issue in issues {
                         issue_json_objects.push(json!({
-                            "severity": "ERROR",
+                            "severity": issue.severity.to_string().to_uppercase(),
                             "line_from": issue.pos.start_line,
                             "line_to": issue.pos.end_line,
                             "type": format!("{}", issue.kind),
@@ -51,7 +53,18 @@ impl ScannerAndAnalyzer {
                             "column_from": issue.pos.start_column,
                             "column_to": issue.pos.end_column,
                             "shortcode": 0,
-                            "taint_trace": serde_json::Value::Null,
+                            "taint_trace": if let Some(taint_trace) = &issue.taint_trace {
+                                json!(taint_trace
+                                    .iter()
+                                    .map(|trace_step| get_taint_trace_json(
+                                        trace_step,
+                                        issue.pos.file_path,
+                                        &file_contents
+                                    ))
+                                    .collect::<Vec<_>>())
+                            } else {
+                                serde_json::Value::Null
+                            },
                             "other_references": serde_json::Value::Null,
                         }));
                     }
@@ -71,6 +84,53 @@ impl ScannerAndAnalyzer {
     }
 }
 
+// the playground shows each hop with the snippet of code it occurs in
+fn get_taint_trace_json(
+    trace_step: &TaintTraceStep,
+    file_path: FilePath,
+    file_contents: &str,
+) -> serde_json::Value {
+    let path_kind = trace_step
+        .path_kind
+        .as_ref()
+        .map(|path_kind| path_kind.to_string())
+        .filter(|path_kind| !path_kind.is_empty());
+
+    match &trace_step.pos {
+        Some(pos) if pos.file_path == file_path => {
+            let start_offset = pos.start_offset as usize;
+            let end_offset = pos.end_offset as usize;
+
+            let snippet_from = file_contents[..start_offset]
+                .rfind('\n')
+                .map(|offset| offset + 1)
+                .unwrap_or(0);
+            let snippet_to = file_contents[end_offset..]
+                .find('\n')
+                .map(|offset| offset + end_offset)
+                .unwrap_or(file_contents.len());
+
+            json!({
+                "label": trace_step.label,
+                "path_kind": path_kind,
+                "line_from": pos.start_line,
+                "line_to": pos.end_line,
+                "column_from": pos.start_column,
+                "column_to": pos.end_column,
+                "from": start_offset,
+                "to": end_offset,
+                "snippet": &file_contents[snippet_from..snippet_to],
+                "snippet_from": snippet_from,
+                "snippet_to": snippet_to,
+            })
+        }
+        _ => json!({
+            "label": trace_step.label,
+            "path_kind": path_kind,
+        }),
+    }
+}
+
 impl Default for ScannerAndAnalyzer {
     fn default() -> Self {
         console_error_panic_hook::set_once();
@@ -1,13 +1,19 @@
 use std::error::Error;
+use std::fs;
 use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
 use hakana_analyzer::config::{self, Config};
 use hakana_analyzer::custom_hook::CustomHook;
-use hakana_reflection_info::analysis_result::AnalysisResult;
+use hakana_reflection_info::analysis_result::{AnalysisResult, Replacement};
+use hakana_reflection_info::code_location::{FilePath, HPos};
+use hakana_reflection_info::codebase_info::CodebaseInfo;
+use hakana_reflection_info::issue::{Issue, IssueSeverity};
+use hakana_str::{Interner, StrId};
 use hakana_workhorse::file::FileStatus;
-use hakana_workhorse::{scan_and_analyze_async, SuccessfulScanData};
+use hakana_workhorse::{analyze_single_file, scan_and_analyze_async, SuccessfulScanData};
 use rustc_hash::{FxHashMap, FxHashSet};
 use tokio::sync::RwLock;
 use tokio::time::sleep;
@@ -15,15 +21,22 @@ use tower_lsp::jsonrpc::Result;
 use tower_lsp::lsp_types::*;
 use tower_lsp::{Client, LanguageServer};
 
+// how long to wait after the last edit to an unsaved buffer before re-analyzing
+const UNSAVED_CHANGE_DEBOUNCE: Duration = Duration::from_millis(250);
+
 #[derive(Debug)]
 pub struct Backend {
     client: Client,
     analysis_config: Arc<Config>,
     previous_scan_data: RwLock<Option<SuccessfulScanData>>,
     previous_analysis_result: RwLock<Option<AnalysisResult>>,
+    // the fixes for every issue in each open file, recomputed whenever the analysis is re-run
+    fix_analysis_results: RwLock<FxHashMap<FilePath, AnalysisResult>>,
     all_diagnostics: RwLock<Option<FxHashMap<Url, Vec<Diagnostic>>>>,
     file_changes: RwLock<Option<FxHashMap<String, FileStatus>>>,
     files_with_errors: RwLock<FxHashSet<Url>>,
+    open_files: RwLock<FxHashMap<Url, String>>,
+    unsaved_change_count: AtomicUsize,
 }
 
 impl Backend {
@@ -33,9 +46,12 @@ impl Backend {
             analysis_config,
             previous_scan_data: RwLock::new(None),
             previous_analysis_result: RwLock::new(None),
+            fix_analysis_results: RwLock::new(FxHashMap::default()),
             all_diagnostics: RwLock::new(None),
             file_changes: RwLock::new(None),
             files_with_errors: RwLock::new(FxHashSet::default()),
+            open_files: RwLock::new(FxHashMap::default()),
+            unsaved_change_count: AtomicUsize::new(0),
         }
     }
 }
@@ -50,12 +66,16 @@ impl LanguageServer for Backend {
                 text_document_sync: Some(TextDocumentSyncCapability::Options(
                     TextDocumentSyncOptions {
                         open_close: Some(true),
-                        change: Some(TextDocumentSyncKind::NONE),
+                        change: Some(TextDocumentSyncKind::INCREMENTAL),
                         will_save: Some(false),
                         will_save_wait_until: Some(false),
                         save: Some(TextDocumentSyncSaveOptions::Supported(true)),
                     },
                 )),
+                hover_provider: Some(HoverProviderCapability::Simple(true)),
+                definition_provider: Some(OneOf::Left(true)),
+                references_provider: Some(OneOf::Left(true)),
+                code_action_provider: Some(CodeActionProviderCapability::Simple(true)),
                 ..ServerCapabilities::default()
             },
             ..InitializeResult::default()
@@ -123,10 +143,7 @@ impl LanguageServer for Backend {
 
             let file_path = file_event.uri.path().to_string();
 
-            if file_path.ends_with(".php")
-                || file_path.ends_with(".hack")
-                || file_path.ends_with(".hhi")
-            {
+            if is_hack_file(&file_path) {
                 match change_type {
                     FileChangeType::CREATED => {
                         new_file_statuses.insert(file_path, FileStatus::Added(0, 0));
@@ -175,8 +192,357 @@ impl LanguageServer for Backend {
         }
     }
 
-    async fn hover(&self, _: HoverParams) -> Result<Option<Hover>> {
-        Ok(None)
+    async fn did_open(&self, params: DidOpenTextDocumentParams) {
+        self.open_files
+            .write()
+            .await
+            .insert(params.text_document.uri, params.text_document.text);
+    }
+
+    async fn did_change(&self, params: DidChangeTextDocumentParams) {
+        let uri = params.text_document.uri;
+
+        let file_contents = {
+            let mut open_files = self.open_files.write().await;
+
+            let file_contents = open_files
+                .entry(uri.clone())
+                .or_insert_with(|| fs::read_to_string(uri.path()).unwrap_or_default());
+
+            for content_change in params.content_changes {
+                apply_content_change(file_contents, content_change);
+            }
+
+            file_contents.clone()
+        };
+
+        let file_path = uri.path().to_string();
+
+        if !is_hack_file(&file_path) {
+            return;
+        }
+
+        self.file_changes
+            .write()
+            .await
+            .get_or_insert_with(FxHashMap::default)
+            .insert(file_path, FileStatus::Unsaved(file_contents));
+
+        self.analyze_unsaved_changes().await;
+    }
+
+    async fn did_close(&self, params: DidCloseTextDocumentParams) {
+        let uri = params.text_document.uri;
+
+        let file_contents = if let Some(file_contents) = self.open_files.write().await.remove(&uri)
+        {
+            file_contents
+        } else {
+            return;
+        };
+
+        let file_path = uri.path().to_string();
+
+        // if the buffer was closed without saving we revert to what's on disk
+        if is_hack_file(&file_path)
+            && !matches!(fs::read_to_string(&file_path), Ok(disk_contents) if disk_contents == file_contents)
+        {
+            self.file_changes
+                .write()
+                .await
+                .get_or_insert_with(FxHashMap::default)
+                .insert(file_path, FileStatus::Modified(0, 0));
+
+            self.analyze_unsaved_changes().await;
+        }
+    }
+
+    async fn hover(&self, params: HoverParams) -> Result<Option<Hover>> {
+        let uri = params.text_document_position_params.text_document.uri;
+        let position = params.text_document_position_params.position;
+
+        let previous_scan_data_guard = self.previous_scan_data.read().await;
+        let previous_analysis_result_guard = self.previous_analysis_result.read().await;
+
+        let (scan_data, analysis_result) = if let (Some(scan_data), Some(analysis_result)) = (
+            previous_scan_data_guard.as_ref(),
+            previous_analysis_result_guard.as_ref(),
+        ) {
+            (scan_data, analysis_result)
+        } else {
+            return Ok(None);
+        };
+
+        let (file_path, offset) = if let Some(file_offset) = get_file_offset(
+            scan_data,
+            &uri,
+            &position,
+            self.get_file_contents(&uri).await,
+        ) {
+            file_offset
+        } else {
+            return Ok(None);
+        };
+
+        let hover_text = if let Some(symbol) =
+            get_symbol_at_offset(scan_data, analysis_result, &file_path, offset)
+        {
+            get_symbol_signature(&scan_data.codebase, &scan_data.interner, &symbol)
+        } else {
+            None
+        };
+
+        let hover_text = if let Some(hover_text) = hover_text {
+            hover_text
+        } else if let Some(expr_type) = analysis_result
+            .expr_types
+            .get(&file_path)
+            .and_then(|expr_types| get_innermost_entry(expr_types, offset))
+        {
+            expr_type.get_id(Some(&scan_data.interner))
+        } else {
+            return Ok(None);
+        };
+
+        Ok(Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: format!("```hack\n{}\n```", hover_text),
+            }),
+            range: None,
+        }))
+    }
+
+    async fn goto_definition(
+        &self,
+        params: GotoDefinitionParams,
+    ) -> Result<Option<GotoDefinitionResponse>> {
+        let uri = params.text_document_position_params.text_document.uri;
+        let position = params.text_document_position_params.position;
+
+        let previous_scan_data_guard = self.previous_scan_data.read().await;
+        let previous_analysis_result_guard = self.previous_analysis_result.read().await;
+
+        let (scan_data, analysis_result) = if let (Some(scan_data), Some(analysis_result)) = (
+            previous_scan_data_guard.as_ref(),
+            previous_analysis_result_guard.as_ref(),
+        ) {
+            (scan_data, analysis_result)
+        } else {
+            return Ok(None);
+        };
+
+        let (file_path, offset) = if let Some(file_offset) = get_file_offset(
+            scan_data,
+            &uri,
+            &position,
+            self.get_file_contents(&uri).await,
+        ) {
+            file_offset
+        } else {
+            return Ok(None);
+        };
+
+        let symbol = if let Some(symbol) =
+            get_symbol_at_offset(scan_data, analysis_result, &file_path, offset)
+        {
+            symbol
+        } else {
+            return Ok(None);
+        };
+
+        let def_pos = if let Some(def_pos) = get_symbol_definition_pos(&scan_data.codebase, &symbol)
+        {
+            def_pos
+        } else {
+            return Ok(None);
+        };
+
+        Ok(self
+            .get_location(&scan_data.interner, &def_pos)
+            .await
+            .map(GotoDefinitionResponse::Scalar))
+    }
+
+    async fn references(&self, params: ReferenceParams) -> Result<Option<Vec<Location>>> {
+        let uri = params.text_document_position.text_document.uri;
+        let position = params.text_document_position.position;
+
+        let previous_scan_data_guard = self.previous_scan_data.read().await;
+        let previous_analysis_result_guard = self.previous_analysis_result.read().await;
+
+        let (scan_data, analysis_result) = if let (Some(scan_data), Some(analysis_result)) = (
+            previous_scan_data_guard.as_ref(),
+            previous_analysis_result_guard.as_ref(),
+        ) {
+            (scan_data, analysis_result)
+        } else {
+            return Ok(None);
+        };
+
+        let (file_path, offset) = if let Some(file_offset) = get_file_offset(
+            scan_data,
+            &uri,
+            &position,
+            self.get_file_contents(&uri).await,
+        ) {
+            file_offset
+        } else {
+            return Ok(None);
+        };
+
+        let symbol = if let Some(symbol) =
+            get_symbol_at_offset(scan_data, analysis_result, &file_path, offset)
+        {
+            symbol
+        } else {
+            return Ok(None);
+        };
+
+        let mut locations = vec![];
+
+        if params.context.include_declaration {
+            if let Some(def_pos) = get_symbol_definition_pos(&scan_data.codebase, &symbol) {
+                if let Some(location) = self.get_location(&scan_data.interner, &def_pos).await {
+                    locations.push(location);
+                }
+            }
+        }
+
+        for (use_file_path, symbol_uses) in &analysis_result.symbol_uses {
+            let mut use_offsets = symbol_uses
+                .iter()
+                .filter(|(_, use_symbol)| **use_symbol == symbol)
+                .map(|(offsets, _)| *offsets)
+                .collect::<Vec<_>>();
+
+            if use_offsets.is_empty() {
+                continue;
+            }
+
+            use_offsets.sort();
+
+            let use_file_path_str = scan_data.interner.lookup(&use_file_path.0);
+
+            let url = if let Ok(url) = Url::from_file_path(use_file_path_str) {
+                url
+            } else {
+                continue;
+            };
+
+            let file_contents = if let Some(file_contents) = self.get_file_contents(&url).await {
+                file_contents
+            } else {
+                continue;
+            };
+
+            for (start_offset, end_offset) in use_offsets {
+                locations.push(Location::new(
+                    url.clone(),
+                    Range {
+                        start: get_position_for_offset(&file_contents, start_offset),
+                        end: get_position_for_offset(&file_contents, end_offset),
+                    },
+                ));
+            }
+        }
+
+        Ok(Some(locations))
+    }
+
+    async fn code_action(&self, params: CodeActionParams) -> Result<Option<CodeActionResponse>> {
+        let uri = params.text_document.uri;
+
+        let previous_scan_data_guard = self.previous_scan_data.read().await;
+        let previous_analysis_result_guard = self.previous_analysis_result.read().await;
+
+        let (scan_data, analysis_result) = if let (Some(scan_data), Some(analysis_result)) = (
+            previous_scan_data_guard.as_ref(),
+            previous_analysis_result_guard.as_ref(),
+        ) {
+            (scan_data, analysis_result)
+        } else {
+            return Ok(None);
+        };
+
+        let file_path = if let Some(file_path) = scan_data.interner.get(uri.path()) {
+            FilePath(file_path)
+        } else {
+            return Ok(None);
+        };
+
+        let file_contents = if let Some(file_contents) = self.get_file_contents(&uri).await {
+            file_contents
+        } else {
+            return Ok(None);
+        };
+
+        let start_offset = get_offset_for_position(&file_contents, &params.range.start);
+        let end_offset = get_offset_for_position(&file_contents, &params.range.end);
+
+        let emitted_issues =
+            if let Some(emitted_issues) = analysis_result.emitted_issues.get(&file_path) {
+                emitted_issues
+            } else {
+                return Ok(None);
+            };
+
+        let issues = emitted_issues
+            .iter()
+            .filter(|issue| {
+                issue.pos.start_offset <= end_offset && issue.pos.end_offset >= start_offset
+            })
+            .collect::<Vec<_>>();
+
+        let fix_analysis_results = self.fix_analysis_results.read().await;
+        let fix_analysis_result = fix_analysis_results.get(&file_path);
+
+        let mut code_actions = vec![];
+
+        for issue in issues {
+            let fix_edits = if let Some(fix_analysis_result) = fix_analysis_result {
+                get_fix_edits(fix_analysis_result, issue, file_path, &file_contents)
+            } else {
+                vec![]
+            };
+
+            if !fix_edits.is_empty() {
+                code_actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                    title: format!("Fix {}", issue.kind.to_string()),
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![get_diagnostic(issue, &file_contents)]),
+                    edit: Some(WorkspaceEdit::new(
+                        [(uri.clone(), fix_edits)].into_iter().collect(),
+                    )),
+                    is_preferred: Some(true),
+                    ..CodeAction::default()
+                }));
+            }
+
+            if let Some((insertion_offset, fixme_comment)) = issue.get_fixme_insertion() {
+                let insertion_position = get_position_for_offset(&file_contents, insertion_offset);
+
+                code_actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                    title: format!("Add HAKANA_FIXME[{}]", issue.kind.to_string()),
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![get_diagnostic(issue, &file_contents)]),
+                    edit: Some(WorkspaceEdit::new(
+                        [(
+                            uri.clone(),
+                            vec![TextEdit::new(
+                                Range::new(insertion_position, insertion_position),
+                                fixme_comment,
+                            )],
+                        )]
+                        .into_iter()
+                        .collect(),
+                    )),
+                    ..CodeAction::default()
+                }));
+            }
+        }
+
+        Ok(Some(code_actions))
     }
 
     async fn shutdown(&self) -> Result<()> {
@@ -185,6 +551,35 @@ impl LanguageServer for Backend {
 }
 
 impl Backend {
+    async fn get_file_contents(&self, uri: &Url) -> Option<String> {
+        if let Some(file_contents) = self.open_files.read().await.get(uri) {
+            return Some(file_contents.clone());
+        }
+
+        fs::read_to_string(uri.path()).ok()
+    }
+
+    async fn get_location(&self, interner: &Interner, pos: &HPos) -> Option<Location> {
+        let url = Url::from_file_path(interner.lookup(&pos.file_path.0)).ok()?;
+        let file_contents = self.get_file_contents(&url).await?;
+
+        Some(Location::new(url, get_range(&file_contents, pos)))
+    }
+
+    async fn analyze_unsaved_changes(&self) {
+        let change_count = self.unsaved_change_count.fetch_add(1, Ordering::SeqCst) + 1;
+
+        sleep(UNSAVED_CHANGE_DEBOUNCE).await;
+
+        // a newer edit has arrived, so let that one trigger the analysis
+        if self.unsaved_change_count.load(Ordering::SeqCst) != change_count {
+            return;
+        }
+
+        self.do_analysis().await;
+        self.emit_issues().await;
+    }
+
     async fn do_analysis(&self) {
         let mut previous_scan_data_guard = self.previous_scan_data.write().await;
         let mut previous_analysis_result_guard = self.previous_analysis_result.write().await;
@@ -192,6 +587,8 @@ impl Backend {
 
         let successful_scan_data = previous_scan_data_guard.take();
 
+        self.fix_analysis_results.write().await.clear();
+
         let analysis_result = previous_analysis_result_guard.take();
 
         let mut file_changes_guard = self.file_changes.write().await;
@@ -201,7 +598,12 @@ impl Backend {
         self.client
             .log_message(
                 MessageType::INFO,
-                format!("scan & analyze changes — {:?}", file_changes),
+                format!(
+                    "scan & analyze changes — {:?}",
+                    file_changes
+                        .as_ref()
+                        .map(|file_changes| file_changes.keys().collect::<Vec<_>>())
+                ),
             )
             .await;
 
@@ -232,30 +634,16 @@ impl Backend {
                     &self.analysis_config.root_dir,
                     false,
                 ) {
-                    let mut diagnostics = vec![];
-                    for emitted_issue in emitted_issues {
-                        diagnostics.push(Diagnostic::new(
-                            Range {
-                                start: Position {
-                                    line: emitted_issue.pos.start_line - 1,
-                                    character: emitted_issue.pos.start_column as u32 - 1,
-                                },
-                                end: Position {
-                                    line: emitted_issue.pos.end_line - 1,
-                                    character: emitted_issue.pos.end_column as u32 - 1,
-                                },
-                            },
-                            Some(DiagnosticSeverity::ERROR),
-                            Some(NumberOrString::String(emitted_issue.kind.to_string())),
-                            Some("Hakana".to_string()),
-                            emitted_issue.description.clone(),
-                            None,
-                            None,
-                        ));
-                    }
-
                     match Url::from_file_path(&file) {
                         Ok(url) => {
+                            let file_contents =
+                                self.get_file_contents(&url).await.unwrap_or_default();
+
+                            let mut diagnostics = vec![];
+                            for emitted_issue in emitted_issues {
+                                diagnostics.push(get_diagnostic(emitted_issue, &file_contents));
+                            }
+
                             all_diagnostics.insert(url, diagnostics);
                         }
                         Err(_) => {
@@ -269,6 +657,34 @@ impl Backend {
                     }
                 }
 
+                // code actions are only requested for open files, so only those get their
+                // fixes computed up front
+                let open_file_paths = self
+                    .open_files
+                    .read()
+                    .await
+                    .keys()
+                    .filter_map(|uri| successful_scan_data.interner.get(uri.path()))
+                    .map(FilePath)
+                    .collect::<Vec<_>>();
+
+                let mut fix_analysis_results = FxHashMap::default();
+
+                for file_path in open_file_paths {
+                    if let Some(emitted_issues) = analysis_result.emitted_issues.get(&file_path) {
+                        fix_analysis_results.insert(
+                            file_path,
+                            get_fix_analysis_result(
+                                file_path,
+                                emitted_issues,
+                                &successful_scan_data,
+                                &self.analysis_config,
+                            ),
+                        );
+                    }
+                }
+
+                *self.fix_analysis_results.write().await = fix_analysis_results;
                 *all_diagnostics_guard = Some(all_diagnostics);
                 *previous_scan_data_guard = Some(successful_scan_data);
                 *previous_analysis_result_guard = Some(analysis_result);
@@ -340,8 +756,9 @@ pub fn get_config(
     config.find_unused_definitions = true;
     config.ignore_mixed_issues = true;
     config.ast_diff = true;
+    config.in_language_server = true;
 
-    config.hooks = plugins;
+    config.hooks = plugins.into_iter().map(Arc::from).collect();
 
     let config_path_str = format!("{}/hakana.json", cwd);
 
@@ -353,3 +770,387 @@ pub fn get_config(
 
     Ok(config)
 }
+
+fn is_hack_file(file_path: &str) -> bool {
+    file_path.ends_with(".php") || file_path.ends_with(".hack") || file_path.ends_with(".hhi")
+}
+
+fn apply_content_change(
+    file_contents: &mut String,
+    content_change: TextDocumentContentChangeEvent,
+) {
+    if let Some(range) = content_change.range {
+        let start_offset = get_offset_for_position(file_contents, &range.start) as usize;
+        let end_offset = get_offset_for_position(file_contents, &range.end) as usize;
+
+        file_contents.replace_range(start_offset..end_offset, &content_change.text);
+    } else {
+        *file_contents = content_change.text;
+    }
+}
+
+fn get_file_offset(
+    scan_data: &SuccessfulScanData,
+    uri: &Url,
+    position: &Position,
+    file_contents: Option<String>,
+) -> Option<(FilePath, u32)> {
+    let file_path = FilePath(scan_data.interner.get(uri.path())?);
+
+    Some((
+        file_path,
+        get_offset_for_position(&file_contents?, position),
+    ))
+}
+
+fn get_diagnostic(issue: &Issue, file_contents: &str) -> Diagnostic {
+    Diagnostic::new(
+        get_range(file_contents, &issue.pos),
+        Some(match issue.severity {
+            IssueSeverity::Warning => DiagnosticSeverity::WARNING,
+            IssueSeverity::Info => DiagnosticSeverity::INFORMATION,
+            IssueSeverity::Error | IssueSeverity::Off => DiagnosticSeverity::ERROR,
+        }),
+        Some(NumberOrString::String(issue.kind.to_string())),
+        Some("Hakana".to_string()),
+        issue.description.clone(),
+        None,
+        None,
+    )
+}
+
+// the replacements and insertions that fix the given issue, as editor text edits
+// the fixes come from re-analyzing the file the same way `hakana fix` would, but without
+// writing anything to disk
+fn get_fix_analysis_result(
+    file_path: FilePath,
+    emitted_issues: &[Issue],
+    scan_data: &SuccessfulScanData,
+    analysis_config: &Config,
+) -> AnalysisResult {
+    let mut fix_config = analysis_config.clone();
+    fix_config.in_language_server = false;
+    fix_config.issues_to_fix = emitted_issues
+        .iter()
+        .map(|issue| issue.kind.clone())
+        .collect();
+
+    analyze_single_file(file_path, scan_data, &Arc::new(fix_config))
+}
+
+fn get_fix_edits(
+    fix_analysis_result: &AnalysisResult,
+    issue: &Issue,
+    file_path: FilePath,
+    file_contents: &str,
+) -> Vec<TextEdit> {
+    let mut fix_edits = vec![];
+
+    let overlaps_issue =
+        |start: u32, end: u32| start <= issue.pos.end_offset && end >= issue.pos.start_offset;
+
+    if let Some(replacements) = fix_analysis_result.replacements.get(&file_path) {
+        for (offsets, replacement) in replacements {
+            if overlaps_issue(offsets.0, offsets.1) {
+                fix_edits.push(get_replacement_edit(file_contents, *offsets, replacement));
+            }
+        }
+    }
+
+    if let Some(insertions) = fix_analysis_result.insertions.get(&file_path) {
+        for (offset, insertion) in insertions {
+            if overlaps_issue(*offset, *offset) {
+                let insertion_position = get_position_for_offset(file_contents, *offset);
+
+                fix_edits.push(TextEdit::new(
+                    Range::new(insertion_position, insertion_position),
+                    insertion.join(""),
+                ));
+            }
+        }
+    }
+
+    fix_edits
+}
+
+// mirrors how `hakana fix` applies each kind of replacement to a file
+fn get_replacement_edit(
+    file_contents: &str,
+    (mut start, mut end): (u32, u32),
+    replacement: &Replacement,
+) -> TextEdit {
+    let new_text = match replacement {
+        Replacement::Remove => "".to_string(),
+        Replacement::TrimPrecedingWhitespace(beg_of_line) => {
+            let potential_whitespace = file_contents
+                .get((*beg_of_line as usize)..start as usize)
+                .unwrap_or_default();
+
+            if potential_whitespace.trim() == "" {
+                start = *beg_of_line;
+
+                if start > 0
+                    && file_contents.get((start as usize - 1)..start as usize) == Some("\n")
+                {
+                    start -= 1;
+                }
+            }
+
+            if file_contents.get(end as usize..end as usize + 1) == Some(",") {
+                end += 1;
+            }
+
+            "".to_string()
+        }
+        Replacement::TrimTrailingWhitespace(end_of_line) => {
+            let trimmed = file_contents
+                .get(end as usize..(*end_of_line as usize))
+                .unwrap_or_default()
+                .trim()
+                .to_string();
+
+            end = *end_of_line;
+
+            trimmed
+        }
+        Replacement::Substitute(string) => string.clone(),
+    };
+
+    TextEdit::new(
+        Range::new(
+            get_position_for_offset(file_contents, start),
+            get_position_for_offset(file_contents, end),
+        ),
+        new_text,
+    )
+}
+
+// positions are in UTF-16 code units, whereas HPos columns count bytes
+fn get_range(file_contents: &str, pos: &HPos) -> Range {
+    Range {
+        start: get_position_for_offset(file_contents, pos.start_offset),
+        end: get_position_for_offset(file_contents, pos.end_offset),
+    }
+}
+
+// LSP positions count UTF-16 code units, whereas Hakana offsets are in bytes
+fn get_position_for_offset(file_contents: &str, offset: u32) -> Position {
+    let mut line_start_offset = 0;
+
+    for (i, line) in file_contents.split_inclusive('\n').enumerate() {
+        if offset < line_start_offset + line.len() as u32 {
+            let line_offset = (offset - line_start_offset) as usize;
+
+            return Position {
+                line: i as u32,
+                character: line
+                    .get(..line_offset)
+                    .map(|line_prefix| line_prefix.encode_utf16().count())
+                    .unwrap_or(line_offset) as u32,
+            };
+        }
+
+        line_start_offset += line.len() as u32;
+    }
+
+    if let Some(last_line) = file_contents.split_inclusive('\n').last() {
+        if !last_line.ends_with('\n') {
+            return Position {
+                line: file_contents.lines().count() as u32 - 1,
+                character: last_line.encode_utf16().count() as u32,
+            };
+        }
+    }
+
+    Position {
+        line: file_contents.lines().count() as u32,
+        character: 0,
+    }
+}
+
+fn get_offset_for_position(file_contents: &str, position: &Position) -> u32 {
+    let mut offset = 0;
+
+    for (i, line) in file_contents.split_inclusive('\n').enumerate() {
+        if i as u32 == position.line {
+            let mut character = 0;
+
+            for (line_offset, c) in line.char_indices() {
+                if character >= position.character || c == '\n' {
+                    return (offset + line_offset) as u32;
+                }
+
+                character += c.len_utf16() as u32;
+            }
+
+            return (offset + line.len()) as u32;
+        }
+
+        offset += line.len();
+    }
+
+    offset as u32
+}
+
+// finds the smallest range that contains the given offset
+fn get_innermost_entry<T>(entries: &FxHashMap<(u32, u32), T>, offset: u32) -> Option<&T> {
+    entries
+        .iter()
+        .filter(|((start_offset, end_offset), _)| *start_offset <= offset && offset < *end_offset)
+        .min_by_key(|((start_offset, end_offset), _)| end_offset - start_offset)
+        .map(|(_, entry)| entry)
+}
+
+// finds the symbol used at the given offset, falling back to a symbol whose name is being defined there
+fn get_symbol_at_offset(
+    scan_data: &SuccessfulScanData,
+    analysis_result: &AnalysisResult,
+    file_path: &FilePath,
+    offset: u32,
+) -> Option<(StrId, StrId)> {
+    if let Some(symbol) = analysis_result
+        .symbol_uses
+        .get(file_path)
+        .and_then(|symbol_uses| get_innermost_entry(symbol_uses, offset))
+    {
+        return Some(*symbol);
+    }
+
+    let file_info = scan_data.codebase.files.get(file_path)?;
+
+    for node in &file_info.ast_nodes {
+        if offset < node.start_offset || offset > node.end_offset {
+            continue;
+        }
+
+        let mut symbol = (node.name, StrId::EMPTY);
+
+        for child in &node.children {
+            if offset >= child.start_offset && offset <= child.end_offset {
+                symbol = (node.name, child.name);
+                break;
+            }
+        }
+
+        if let Some(def_pos) = get_symbol_definition_pos(&scan_data.codebase, &symbol) {
+            if def_pos.file_path == *file_path
+                && offset >= def_pos.start_offset
+                && offset < def_pos.end_offset
+            {
+                return Some(symbol);
+            }
+        }
+    }
+
+    None
+}
+
+fn get_symbol_definition_pos(codebase: &CodebaseInfo, symbol: &(StrId, StrId)) -> Option<HPos> {
+    if let Some(functionlike_info) = codebase.functionlike_infos.get(symbol) {
+        return Some(
+            functionlike_info
+                .name_location
+                .unwrap_or(functionlike_info.def_location),
+        );
+    }
+
+    if symbol.1 == StrId::EMPTY {
+        if let Some(classlike_info) = codebase.classlike_infos.get(&symbol.0) {
+            return Some(classlike_info.name_location);
+        }
+
+        if let Some(constant_info) = codebase.constant_infos.get(&symbol.0) {
+            return Some(constant_info.pos);
+        }
+
+        return codebase
+            .type_definitions
+            .get(&symbol.0)
+            .map(|type_definition_info| type_definition_info.location);
+    }
+
+    let classlike_info = codebase.classlike_infos.get(&symbol.0)?;
+
+    if let Some(declaring_class) = codebase.get_declaring_class_for_property(&symbol.0, &symbol.1) {
+        if let Some(property_info) = codebase.get_property_storage(declaring_class, &symbol.1) {
+            return property_info.pos;
+        }
+    }
+
+    for classlike_name in std::iter::once(&symbol.0)
+        .chain(classlike_info.all_parent_classes.iter())
+        .chain(classlike_info.all_parent_interfaces.iter())
+    {
+        if let Some(constant_info) = codebase
+            .classlike_infos
+            .get(classlike_name)
+            .and_then(|classlike_info| classlike_info.constants.get(&symbol.1))
+        {
+            return Some(constant_info.pos);
+        }
+    }
+
+    None
+}
+
+fn get_symbol_signature(
+    codebase: &CodebaseInfo,
+    interner: &Interner,
+    symbol: &(StrId, StrId),
+) -> Option<String> {
+    if let Some(functionlike_info) = codebase.functionlike_infos.get(symbol) {
+        let params = functionlike_info
+            .params
+            .iter()
+            .map(|param| {
+                format!(
+                    "{}{}{}",
+                    if let Some(signature_type) = &param.signature_type {
+                        signature_type.get_id(Some(interner)) + " "
+                    } else {
+                        "".to_string()
+                    },
+                    if param.is_variadic { "..." } else { "" },
+                    interner.lookup(&param.name.0)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let return_type = if let Some(return_type) = &functionlike_info.return_type {
+            format!(": {}", return_type.get_id(Some(interner)))
+        } else {
+            "".to_string()
+        };
+
+        return Some(if symbol.1 == StrId::EMPTY {
+            format!(
+                "function {}({}){}",
+                interner.lookup(&symbol.0),
+                params,
+                return_type
+            )
+        } else {
+            format!(
+                "{}::{}({}){}",
+                interner.lookup(&symbol.0),
+                interner.lookup(&symbol.1),
+                params,
+                return_type
+            )
+        });
+    }
+
+    if symbol.1 != StrId::EMPTY {
+        if let Some(property_info) = codebase.get_property_storage(&symbol.0, &symbol.1) {
+            return Some(format!(
+                "{}::${}: {}",
+                interner.lookup(&symbol.0),
+                interner.lookup(&symbol.1),
+                property_info.type_.get_id(Some(interner))
+            ));
+        }
+    }
+
+    None
+}
@@ -32,6 +32,9 @@ fn main() -> Result<()> {
         "HH\\FIXME\\UNSAFE_CAST",
         "HH\\Facts\\enabled",
         "HH\\FormatString",
+        "HH\\ImmMap",
+        "HH\\ImmSet",
+        "HH\\ImmVector",
         "HH\\Iterator",
         "HH\\KeyedContainer",
         "HH\\KeyedIterator",
@@ -204,9 +207,12 @@ fn main() -> Result<()> {
         "HH\\Lib\\Vec\\zip",
         "HH\\Lib\\_Private\\regex_match",
         "HH\\Lib\\_Private\\validate_offset",
+        "HH\\Map",
         "HH\\MemberOf",
+        "HH\\Pair",
         "HH\\ReifiedGenerics\\get_classname",
         "HH\\ReifiedGenerics\\get_type_structure",
+        "HH\\Set",
         "HH\\Shapes",
         "HH\\Traversable",
         "HH\\TypeStructure",
@@ -244,12 +250,14 @@ fn main() -> Result<()> {
         "Hakana\\MustUse",
         "Hakana\\SecurityAnalysis\\IgnorePath",
         "Hakana\\SecurityAnalysis\\IgnorePathIfTrue",
+        "Hakana\\SecurityAnalysis\\LoadsTaint",
         "Hakana\\SecurityAnalysis\\RemoveTaintsWhenReturningTrue",
         "Hakana\\SecurityAnalysis\\Sanitize",
         "Hakana\\SecurityAnalysis\\ShapeSource",
         "Hakana\\SecurityAnalysis\\Sink",
         "Hakana\\SecurityAnalysis\\Source",
         "Hakana\\SecurityAnalysis\\SpecializeCall",
+        "Hakana\\SecurityAnalysis\\StoresTaint",
         "Hakana\\SpecialTypes\\LiteralString",
         "Hakana\\TestOnly",
         "NumberFormatter",
@@ -259,6 +267,7 @@ fn main() -> Result<()> {
         "SimpleXMLElement",
         "XHPChild",
         "__DIR__",
+        "__Deprecated",
         "__DynamicallyCallable",
         "__EntryPoint",
         "__FILE__",
@@ -319,6 +328,7 @@ fn main() -> Result<()> {
         "dirname",
         "echo",
         "escapeshellarg",
+        "exit",
         "explode",
         "extension",
         "fb_serialize",